@@ -0,0 +1,342 @@
+//! Generates the product impls for the multivector types from their Cayley tables
+//!
+//! Every product is derived from the basis blades and the metric of the algebra, so fixing or
+//! extending the algebra only means changing the description at the bottom of this file
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+struct Algebra {
+    /// The name of the generic struct
+    name: &'static str,
+    /// The square of each basis vector
+    metric: &'static [i32],
+    /// The field name and basis vectors of each blade, in field order
+    blades: &'static [(&'static str, &'static [usize])],
+}
+
+#[derive(Clone, Copy)]
+enum Product {
+    Geometric,
+    Outer,
+    Regressive,
+    Inner,
+    LeftContraction,
+    RightContraction,
+}
+
+impl Product {
+    const ALL: [Product; 6] = [
+        Product::Geometric,
+        Product::Outer,
+        Product::Regressive,
+        Product::Inner,
+        Product::LeftContraction,
+        Product::RightContraction,
+    ];
+
+    fn op(self) -> (&'static str, &'static str) {
+        match self {
+            Product::Geometric => ("Mul", "mul"),
+            Product::Outer => ("BitXor", "bitxor"),
+            Product::Regressive => ("BitAnd", "bitand"),
+            Product::Inner => ("BitOr", "bitor"),
+            Product::LeftContraction => ("Shl", "shl"),
+            Product::RightContraction => ("Shr", "shr"),
+        }
+    }
+
+    fn doc(self) -> &'static str {
+        match self {
+            Product::Geometric => "The geometric product",
+            Product::Outer => {
+                "The outer (wedge) product, the meet of two elements, e.g. the intersection point of two lines"
+            }
+            Product::Regressive => {
+                "The regressive (vee) product, the join of two elements, e.g. the line through two points"
+            }
+            Product::Inner => "The symmetric inner product",
+            Product::LeftContraction => "The left contraction",
+            Product::RightContraction => "The right contraction",
+        }
+    }
+}
+
+/// A term `sign * lhs[left] * rhs[right]` of one output component
+#[derive(Clone, Copy)]
+struct Term {
+    sign: i32,
+    left: usize,
+    right: usize,
+}
+
+/// Multiplies two basis blades, returning the sign and the basis vectors of the result
+fn blade_product(metric: &[i32], a: &[usize], b: &[usize]) -> (i32, Vec<usize>) {
+    let mut vectors = a.iter().chain(b).copied().collect::<Vec<_>>();
+    let mut sign = 1;
+    for i in 0..vectors.len() {
+        for j in 0..vectors.len() - 1 - i {
+            if vectors[j] > vectors[j + 1] {
+                vectors.swap(j, j + 1);
+                sign = -sign;
+            }
+        }
+    }
+    let mut result = vec![];
+    let mut i = 0;
+    while i < vectors.len() {
+        if i + 1 < vectors.len() && vectors[i] == vectors[i + 1] {
+            sign *= metric[vectors[i]];
+            i += 2;
+        } else {
+            result.push(vectors[i]);
+            i += 1;
+        }
+    }
+    (sign, result)
+}
+
+impl Algebra {
+    fn blade_index(&self, vectors: &[usize]) -> usize {
+        self.blades
+            .iter()
+            .position(|&(_, blade)| blade == vectors)
+            .unwrap()
+    }
+
+    fn pseudoscalar(&self) -> usize {
+        self.blades.len() - 1
+    }
+
+    /// The sign and blade `c` such that `blade ^ c` is the pseudoscalar
+    fn right_complement(&self, blade: usize) -> (i32, usize) {
+        (0..self.blades.len())
+            .find_map(|other| {
+                let (sign, vectors) = self.outer(blade, other)?;
+                (self.blade_index(&vectors) == self.pseudoscalar()).then_some((sign, other))
+            })
+            .unwrap()
+    }
+
+    /// The sign and blade `c` such that `c ^ blade` is the pseudoscalar
+    fn left_complement(&self, blade: usize) -> (i32, usize) {
+        (0..self.blades.len())
+            .find_map(|other| {
+                let (sign, vectors) = self.outer(other, blade)?;
+                (self.blade_index(&vectors) == self.pseudoscalar()).then_some((sign, other))
+            })
+            .unwrap()
+    }
+
+    fn outer(&self, a: usize, b: usize) -> Option<(i32, Vec<usize>)> {
+        let (a, b) = (self.blades[a].1, self.blades[b].1);
+        // the metric doesn't matter as the blades can't share any vectors
+        let (sign, vectors) = blade_product(&vec![1; self.metric.len()], a, b);
+        (vectors.len() == a.len() + b.len()).then_some((sign, vectors))
+    }
+
+    /// The terms of each output component of `product`, in field order
+    fn table(&self, product: Product) -> Vec<Vec<Term>> {
+        let mut table = vec![vec![]; self.blades.len()];
+        for left in 0..self.blades.len() {
+            for right in 0..self.blades.len() {
+                let (sign, output) = match product {
+                    Product::Regressive => {
+                        // a v b = J^-1(J(a) ^ J(b))
+                        let (left_sign, left_complement) = self.right_complement(left);
+                        let (right_sign, right_complement) = self.right_complement(right);
+                        let Some((sign, vectors)) = self.outer(left_complement, right_complement)
+                        else {
+                            continue;
+                        };
+                        let (output_sign, output) =
+                            self.left_complement(self.blade_index(&vectors));
+                        (left_sign * right_sign * sign * output_sign, output)
+                    }
+                    _ => {
+                        let (a, b) = (self.blades[left].1, self.blades[right].1);
+                        let (sign, vectors) = blade_product(self.metric, a, b);
+                        let grade = vectors.len() as isize;
+                        let (a, b) = (a.len() as isize, b.len() as isize);
+                        let keep = match product {
+                            Product::Geometric => true,
+                            Product::Outer => grade == a + b,
+                            Product::Inner => grade == (a - b).abs(),
+                            Product::LeftContraction => grade == b - a,
+                            Product::RightContraction => grade == a - b,
+                            Product::Regressive => unreachable!(),
+                        };
+                        if !keep {
+                            continue;
+                        }
+                        (sign, self.blade_index(&vectors))
+                    }
+                };
+                if sign != 0 {
+                    table[output].push(Term { sign, left, right });
+                }
+            }
+        }
+        table
+    }
+
+    fn type_parameter(&self, side: &str, index: usize) -> String {
+        format!("{side}{}", self.blades[index].0.to_uppercase())
+    }
+
+    fn struct_type(&self, side: &str) -> String {
+        let parameters = (0..self.blades.len())
+            .map(|index| self.type_parameter(side, index))
+            .collect::<Vec<_>>();
+        format!("{}<{}>", self.name, parameters.join(", "))
+    }
+
+    fn generate_product(&self, product: Product, output: &mut String) {
+        let (trait_name, method) = product.op();
+        let table = self.table(product);
+        let term_type = |term: &Term| {
+            format!(
+                "MO<{}, {}>",
+                self.type_parameter("L", term.left),
+                self.type_parameter("R", term.right)
+            )
+        };
+
+        let mut bounds = vec![];
+        for left in 0..self.blades.len() {
+            let mut bound = "Value".to_string();
+            let mut rights = vec![];
+            for term in table.iter().flatten().filter(|term| term.left == left) {
+                if !rights.contains(&term.right) {
+                    rights.push(term.right);
+                    write!(bound, " + Mul<{}>", self.type_parameter("R", term.right)).unwrap();
+                }
+            }
+            bounds.push(format!("{}: {bound}", self.type_parameter("L", left)));
+        }
+        for right in 0..self.blades.len() {
+            bounds.push(format!("{}: Value", self.type_parameter("R", right)));
+        }
+
+        let mut output_types = vec![];
+        let mut expressions = vec![];
+        for (component, terms) in table.iter().enumerate() {
+            let field = self.blades[component].0;
+            let Some((first, rest)) = terms.split_first() else {
+                output_types.push("Zero".to_string());
+                expressions.push(format!("{field}: Zero"));
+                continue;
+            };
+            let left_field = |term: &Term| self.blades[term.left].0;
+            let right_field = |term: &Term| self.blades[term.right].0;
+            let mut accumulated = term_type(first);
+            let mut expression = format!("self.{} * rhs.{}", left_field(first), right_field(first));
+            if first.sign < 0 {
+                bounds.push(format!("{accumulated}: Neg"));
+                accumulated = format!("NO<{accumulated}>");
+                expression = format!("-({expression})");
+            }
+            for term in rest {
+                let (op, alias, symbol) = if term.sign > 0 {
+                    ("Add", "AO", '+')
+                } else {
+                    ("Sub", "SO", '-')
+                };
+                bounds.push(format!("{accumulated}: {op}<{}>", term_type(term)));
+                accumulated = format!("{alias}<{accumulated}, {}>", term_type(term));
+                write!(
+                    expression,
+                    " {symbol} self.{} * rhs.{}",
+                    left_field(term),
+                    right_field(term)
+                )
+                .unwrap();
+            }
+            bounds.push(format!("{accumulated}: Value"));
+            output_types.push(accumulated);
+            expressions.push(format!("{field}: {expression}"));
+        }
+
+        let left_parameters = (0..self.blades.len()).map(|index| self.type_parameter("L", index));
+        let right_parameters = (0..self.blades.len()).map(|index| self.type_parameter("R", index));
+        let parameters = left_parameters.chain(right_parameters).collect::<Vec<_>>();
+        let rhs_type = self.struct_type("R");
+
+        writeln!(output, "/// {}", product.doc()).unwrap();
+        writeln!(
+            output,
+            "impl<{}> {trait_name}<{rhs_type}> for {} where {} {{",
+            parameters.join(", "),
+            self.struct_type("L"),
+            bounds.join(", ")
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "type Output = {}<{}>;",
+            self.name,
+            output_types.join(", ")
+        )
+        .unwrap();
+        writeln!(output, "#[inline]").unwrap();
+        writeln!(
+            output,
+            "fn {method}(self, rhs: {rhs_type}) -> Self::Output {{ Self::Output {{ {} }} }}",
+            expressions.join(", ")
+        )
+        .unwrap();
+        writeln!(output, "}}").unwrap();
+    }
+
+    fn generate(&self, path: &Path) {
+        let mut output = String::new();
+        for product in Product::ALL {
+            self.generate_product(product, &mut output);
+        }
+        fs::write(path, output).unwrap();
+    }
+}
+
+const PGA2D: Algebra = Algebra {
+    name: "GenericMultiVector",
+    metric: &[0, 1, 1],
+    blades: &[
+        ("s", &[]),
+        ("e0", &[0]),
+        ("e1", &[1]),
+        ("e2", &[2]),
+        ("e01", &[0, 1]),
+        ("e02", &[0, 2]),
+        ("e12", &[1, 2]),
+        ("e012", &[0, 1, 2]),
+    ],
+};
+
+const PGA3D: Algebra = Algebra {
+    name: "GenericMultiVector",
+    metric: &[0, 1, 1, 1],
+    blades: &[
+        ("s", &[]),
+        ("e0", &[0]),
+        ("e1", &[1]),
+        ("e2", &[2]),
+        ("e3", &[3]),
+        ("e01", &[0, 1]),
+        ("e02", &[0, 2]),
+        ("e03", &[0, 3]),
+        ("e12", &[1, 2]),
+        ("e13", &[1, 3]),
+        ("e23", &[2, 3]),
+        ("e012", &[0, 1, 2]),
+        ("e013", &[0, 1, 3]),
+        ("e023", &[0, 2, 3]),
+        ("e123", &[1, 2, 3]),
+        ("e0123", &[0, 1, 2, 3]),
+    ],
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    PGA2D.generate(&Path::new(&out_dir).join("multivector_products.rs"));
+    PGA3D.generate(&Path::new(&out_dir).join("multivector3d_products.rs"));
+}
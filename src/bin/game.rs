@@ -1,9 +1,12 @@
 #![deny(rust_2018_idioms)]
 
-use bevy::prelude::{App, Commands, EventReader, Query, Res, Startup, Update};
+use bevy::prelude::{
+    App, Commands, EventReader, FixedUpdate, Query, Res, Startup, Update, Vec2, With,
+};
 use dash_game::{
+    time::Time,
     window::{MouseButton, MouseButtons, MouseMovement, MouseScroll, WindowSize},
-    Camera, Circle, GamePlugins, Material, Quad, Transform,
+    Camera, Circle, GamePlugins, Layer, Material, Outline, Quad, Transform,
 };
 
 fn main() {
@@ -11,36 +14,38 @@ fn main() {
         .add_plugins(GamePlugins)
         .add_systems(Startup, startup)
         .add_systems(Update, (camera_mouse_movement, camera_zoom))
+        .add_systems(FixedUpdate, spin_quads)
         .run();
 }
 
 fn startup(mut commands: Commands<'_, '_>) {
     let _camera = commands.spawn((
-        Transform { x: 0.0, y: 0.0 },
+        Transform::IDENTITY,
         Camera {
             vertical_height: 2.0,
         },
     ));
     let _quad = commands.spawn((
-        Transform { x: -0.6, y: 0.0 },
+        Transform::from_xy(-0.6, 0.0),
         Quad {
             width: 1.0,
             height: 1.0,
         },
-        Material {
-            red: 1.0,
-            green: 0.2,
-            blue: 0.0,
-        },
+        Material::rgb(1.0, 0.2, 0.0),
     ));
     let _circle = commands.spawn((
-        Transform { x: 0.6, y: 0.0 },
+        Transform::from_xy(0.4, 0.0),
         Circle { radius: 0.5 },
-        Material {
-            red: 0.2,
-            green: 0.0,
-            blue: 1.0,
-        },
+        // behind the quad, even though circles are drawn after quads
+        Layer(-1),
+        Material::rgba(0.2, 0.0, 1.0, 0.8),
+    ));
+    let _range = commands.spawn((
+        Transform::from_xy(-0.6, 0.0),
+        Circle { radius: 0.9 },
+        Layer(1),
+        Material::rgba(1.0, 1.0, 1.0, 0.0),
+        Outline::rgb(0.02, 1.0, 1.0, 1.0),
     ));
 }
 
@@ -54,11 +59,15 @@ fn camera_mouse_movement(
     let aspect = size.width().get() as f32 / size.height().get() as f32;
     for mouse_movement in mouse_movement_events.iter() {
         if mouse_buttons.is_button_down(MouseButton::Right) {
-            camera_transform.x += -mouse_movement.delta_x as f32 / size.width().get() as f32
-                * camera.vertical_height
-                * aspect;
-            camera_transform.y +=
-                mouse_movement.delta_y as f32 / size.height().get() as f32 * camera.vertical_height;
+            // the view turns and zooms with the camera, so the drag is in the camera's own space
+            let delta = camera_transform.to_affine().transform_vector2(Vec2::new(
+                -mouse_movement.delta_x as f32 / size.width().get() as f32
+                    * camera.vertical_height
+                    * aspect,
+                mouse_movement.delta_y as f32 / size.height().get() as f32 * camera.vertical_height,
+            ));
+            camera_transform.x += delta.x;
+            camera_transform.y += delta.y;
         }
     }
 }
@@ -75,3 +84,9 @@ fn camera_zoom(
         }
     }
 }
+
+fn spin_quads(mut quads: Query<'_, '_, &mut Transform, With<Quad>>, time: Res<'_, Time>) {
+    for mut transform in &mut quads {
+        transform.rotation += time.delta_seconds();
+    }
+}
@@ -7,32 +7,61 @@ struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) circle_index: u32,
     @location(1) uv: vec2<f32>,
+    @location(2) local_coord: vec2<f32>,
+}
+
+struct Transform {
+    translation_x: f32,
+    translation_y: f32,
+    x_axis_x: f32,
+    x_axis_y: f32,
+    y_axis_x: f32,
+    y_axis_y: f32,
 }
 
 struct Camera {
-    x: f32,
-    y: f32,
-    aspect: f32,
-    vertical_height: f32,
+    world_to_clip: Transform,
 }
 
 @group(0)
 @binding(0)
 var<uniform> camera: Camera;
 
+fn apply_transform(transform: Transform, coord: vec2<f32>) -> vec2<f32> {
+    let x_axis = vec2<f32>(transform.x_axis_x, transform.x_axis_y);
+    let y_axis = vec2<f32>(transform.y_axis_x, transform.y_axis_y);
+    return vec2<f32>(transform.translation_x, transform.translation_y) + x_axis * coord.x + y_axis * coord.y;
+}
+
 struct Circle {
-    x: f32,
-    y: f32,
+    transform: Transform,
+    depth: f32,
     radius: f32,
     red: f32,
     green: f32,
     blue: f32,
+    alpha: f32,
+    outline_width: f32,
+    outline_red: f32,
+    outline_green: f32,
+    outline_blue: f32,
+    outline_alpha: f32,
 }
 
 @group(1)
 @binding(0)
 var<storage, read> circles: array<Circle>;
 
+// draws a stroke `width` wide along the inside of an edge that is `distance` away, never thinner
+// than a pixel and anti-aliased on the inner side, over the premultiplied `fill`
+fn apply_outline(fill: vec4<f32>, distance: f32, width: f32, color: vec4<f32>) -> vec4<f32> {
+    let pixel = fwidth(distance);
+    let stroke_width = max(width, pixel);
+    let coverage = select(0.0, 1.0 - smoothstep(stroke_width - pixel * 0.5, stroke_width + pixel * 0.5, -distance), width > 0.0);
+    let stroke = vec4<f32>(color.rgb * color.a, color.a) * coverage;
+    return stroke + fill * (1.0 - stroke.a);
+}
+
 @vertex
 fn vertex(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
@@ -44,19 +73,30 @@ fn vertex(in: VertexInput) -> VertexOutput {
 
     var vertex_coord = out.uv * 2.0 - 1.0;
     vertex_coord *= circles[in.circle_index].radius;
-    vertex_coord.x += circles[in.circle_index].x;
-    vertex_coord.y += circles[in.circle_index].y;
+    out.local_coord = vertex_coord;
+    vertex_coord = apply_transform(circles[in.circle_index].transform, vertex_coord);
 
-    out.clip_position = vec4<f32>((vertex_coord - vec2<f32>(camera.x, camera.y)) / camera.vertical_height * 2.0 / vec2<f32>(camera.aspect, 1.0), 0.0, 1.0);
+    out.clip_position = vec4<f32>(apply_transform(camera.world_to_clip, vertex_coord), circles[in.circle_index].depth, 1.0);
 
     return out;
 }
 
 @fragment
 fn pixel(in: VertexOutput) -> @location(0) vec4<f32> {
-    let coord = in.uv * 2.0 - 1.0;
-    if dot(coord, coord) > 1.0 {
+    // negative inside the circle
+    let distance = length(in.local_coord) - circles[in.circle_index].radius;
+    let color = vec3<f32>(circles[in.circle_index].red, circles[in.circle_index].green, circles[in.circle_index].blue);
+    // premultiplied, which every blend mode is set up for
+    let fill = vec4<f32>(color * circles[in.circle_index].alpha, circles[in.circle_index].alpha);
+    let outline_color = vec4<f32>(circles[in.circle_index].outline_red, circles[in.circle_index].outline_green, circles[in.circle_index].outline_blue, circles[in.circle_index].outline_alpha);
+    let outlined = apply_outline(fill, distance, circles[in.circle_index].outline_width, outline_color);
+    // the edge fades out over the last pixel inside the circle, so the quad around it doesn't cut
+    // off the fade, and circles are always blended so that this shows
+    let pixel = fwidth(distance);
+    let coverage = 1.0 - smoothstep(-pixel, 0.0, distance);
+    // only after the derivatives, as they need the neighbouring pixels to keep running
+    if coverage <= 0.0 {
         discard;
     }
-    return vec4<f32>(circles[in.circle_index].red, circles[in.circle_index].green, circles[in.circle_index].blue, 1.0);
+    return outlined * coverage;
 }
@@ -0,0 +1,194 @@
+//! Conversions between the multivector types and the other math types used around the crate
+
+#[cfg(feature = "mint")]
+use crate::multivector::Number;
+use crate::{
+    multivector::{Line, Motor, Point},
+    Transform,
+};
+use bevy::math::{Mat3, Vec2, Vec3};
+
+impl From<Transform> for Point {
+    /// The position of the transform
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        Point::new(transform.x, transform.y)
+    }
+}
+
+impl From<Point> for Transform {
+    /// Moves to `point`, which shouldn't be ideal
+    #[inline]
+    fn from(point: Point) -> Self {
+        Transform::from_xy(point.x(), point.y())
+    }
+}
+
+impl From<Transform> for Motor {
+    /// The rotation and translation of the transform, motors can't scale so that is lost
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        Motor::from_cartesian(transform.x, transform.y, transform.rotation)
+    }
+}
+
+impl From<Motor> for Transform {
+    #[inline]
+    fn from(motor: Motor) -> Self {
+        let (x, y, rotation) = motor.to_cartesian();
+        Transform::from_xy(x, y).with_rotation(rotation)
+    }
+}
+
+impl From<Vec2> for Point {
+    #[inline]
+    fn from(position: Vec2) -> Self {
+        Point::new(position.x, position.y)
+    }
+}
+
+impl From<Point> for Vec2 {
+    /// This divides by the weight, so `point` shouldn't be ideal
+    #[inline]
+    fn from(point: Point) -> Self {
+        Vec2::new(point.x(), point.y())
+    }
+}
+
+impl From<Vec3> for Line {
+    /// The line `a * x + b * y + c = 0` with the coefficients `(a, b, c)`
+    #[inline]
+    fn from(coefficients: Vec3) -> Self {
+        Line::new(coefficients.x, coefficients.y, coefficients.z)
+    }
+}
+
+impl From<Line> for Vec3 {
+    /// The coefficients `(a, b, c)` of the line `a * x + b * y + c = 0`
+    #[inline]
+    fn from(line: Line) -> Self {
+        Vec3::new(line.e1, line.e2, line.e0)
+    }
+}
+
+impl From<Mat3> for Motor {
+    /// `matrix` is expected to be a 2D rotation and translation, any scale or shear is lost
+    #[inline]
+    fn from(matrix: Mat3) -> Self {
+        let angle = matrix.x_axis.y.atan2(matrix.x_axis.x);
+        Motor::from_cartesian(matrix.z_axis.x, matrix.z_axis.y, angle)
+    }
+}
+
+impl From<Motor> for Mat3 {
+    #[inline]
+    fn from(motor: Motor) -> Self {
+        let (x, y, angle) = motor.to_cartesian();
+        Mat3::from_translation(Vec2::new(x, y)) * Mat3::from_angle(angle)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T: Number> From<mint::Point2<T>> for Point<T> {
+    #[inline]
+    fn from(point: mint::Point2<T>) -> Self {
+        Point::new(point.x, point.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T: Number> From<Point<T>> for mint::Point2<T> {
+    /// This divides by the weight, so `point` shouldn't be ideal
+    #[inline]
+    fn from(point: Point<T>) -> Self {
+        mint::Point2 {
+            x: point.x(),
+            y: point.y(),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<T: Number> From<mint::Vector2<T>> for Point<T> {
+    /// The ideal point in the direction of `vector`
+    #[inline]
+    fn from(vector: mint::Vector2<T>) -> Self {
+        Point::direction(vector.x, vector.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Motor> for mint::ColumnMatrix3<f32> {
+    #[inline]
+    fn from(motor: Motor) -> Self {
+        let [x, y, z] = Mat3::from(motor).to_cols_array_2d();
+        mint::ColumnMatrix3 {
+            x: x.into(),
+            y: y.into(),
+            z: z.into(),
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::ColumnMatrix3<f32>> for Motor {
+    /// `matrix` is expected to be a 2D rotation and translation, any scale or shear is lost
+    #[inline]
+    fn from(matrix: mint::ColumnMatrix3<f32>) -> Self {
+        Mat3::from_cols_array_2d(&[matrix.x.into(), matrix.y.into(), matrix.z.into()]).into()
+    }
+}
+
+#[test]
+fn transform() {
+    let transform = Transform::from_xy(3.0, -2.0);
+    let point = Point::from(transform);
+    assert_eq!(point, Point::new(3.0, -2.0));
+    assert_eq!(Transform::from(point), transform);
+
+    let motor = Motor::from(transform);
+    assert_eq!(motor.transform(Point::new(0.0, 0.0)), point);
+    let transform = Transform::from(Motor::from_cartesian(1.0, 2.0, 0.5));
+    assert!((transform.x - 1.0).abs() < 1e-6 && (transform.y - 2.0).abs() < 1e-6);
+    assert!((transform.rotation - 0.5).abs() < 1e-6);
+
+    // both rotate and move points the same way
+    let transform = transform.with_scale(1.0);
+    let moved = Motor::from(transform).transform(Point::new(1.0, 1.0));
+    let expected = transform.to_affine().transform_point2(Vec2::new(1.0, 1.0));
+    assert!(Vec2::from(moved).abs_diff_eq(expected, 1e-5));
+}
+
+#[test]
+fn bevy_math() {
+    let point = Point::from(Vec2::new(1.5, 4.0));
+    assert_eq!(Vec2::from(point), Vec2::new(1.5, 4.0));
+
+    let line = Line::from(Vec3::new(1.0, -1.0, 2.0));
+    assert_eq!(line, Line::new(1.0, -1.0, 2.0));
+    assert_eq!(Vec3::from(line), Vec3::new(1.0, -1.0, 2.0));
+
+    let motor = Motor::from_cartesian(2.0, -1.0, 0.75);
+    let matrix = Mat3::from(motor);
+    let moved = motor.transform(Point::new(1.0, 1.0));
+    let expected = matrix.transform_point2(Vec2::new(1.0, 1.0));
+    assert!(Vec2::from(moved).abs_diff_eq(expected, 1e-5));
+    assert!(Motor::from(matrix).approx_eq(motor, 1e-5));
+}
+
+#[cfg(feature = "mint")]
+#[test]
+fn mint() {
+    let point = Point::<f64>::from(mint::Point2 { x: 1.0, y: 2.0 });
+    assert_eq!(point, Point::new(1.0, 2.0));
+    let mint::Point2 { x, y } = point.into();
+    assert_eq!((x, y), (1.0, 2.0));
+    assert_eq!(
+        Point::from(mint::Vector2 { x: 1.0, y: 0.0 }),
+        Point::direction(1.0, 0.0)
+    );
+
+    let motor = Motor::from_cartesian(2.0, -1.0, 0.75);
+    let matrix = mint::ColumnMatrix3::from(motor);
+    assert!(Motor::from(matrix).approx_eq(motor, 1e-5));
+}
@@ -0,0 +1,482 @@
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+/// A signed Q32.32 fixed-point number
+///
+/// Every operation is done with integer math, so results are bit-identical on every platform
+///
+/// Results that don't fit saturate to [`Fixed::MIN`] or [`Fixed::MAX`] instead of panicking or
+/// wrapping, the same in debug and release builds. Dividing by zero gives [`Fixed::MAX`] or
+/// [`Fixed::MIN`] by the sign of the dividend, like the infinities of a float, and `0 / 0` gives
+/// zero, as there is no NaN
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const FRACTIONAL_BITS: u32 = 32;
+
+    pub const ZERO: Self = Self(0);
+    pub const ONE: Self = Self(1 << Self::FRACTIONAL_BITS);
+    pub const HALF: Self = Self(1 << (Self::FRACTIONAL_BITS - 1));
+    /// The smallest positive value
+    pub const EPSILON: Self = Self(1);
+    pub const MIN: Self = Self(i64::MIN);
+    pub const MAX: Self = Self(i64::MAX);
+    pub const PI: Self = Self(13493037705);
+    pub const TAU: Self = Self(26986075409);
+    pub const FRAC_PI_2: Self = Self(6746518852);
+    pub const FRAC_PI_4: Self = Self(3373259426);
+
+    /// Clamps the bits of a result computed with extra range into range
+    #[inline]
+    const fn saturating_from_wide(bits: i128) -> Self {
+        if bits > i64::MAX as i128 {
+            Self::MAX
+        } else if bits < i64::MIN as i128 {
+            Self::MIN
+        } else {
+            Self(bits as i64)
+        }
+    }
+
+    #[inline]
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    #[inline]
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    #[inline]
+    pub const fn from_int(value: i32) -> Self {
+        Self((value as i64) << Self::FRACTIONAL_BITS)
+    }
+
+    /// Rounds towards negative infinity
+    #[inline]
+    pub const fn to_int(self) -> i32 {
+        (self.0 >> Self::FRACTIONAL_BITS) as i32
+    }
+
+    /// Converts from a float, truncating any bits that don't fit and saturating values that don't
+    ///
+    /// This is deterministic because scaling by a power of two is exact
+    #[inline]
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * (1u64 << Self::FRACTIONAL_BITS) as f64) as i64)
+    }
+
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / (1u64 << Self::FRACTIONAL_BITS) as f64
+    }
+
+    #[inline]
+    pub fn from_f32(value: f32) -> Self {
+        Self::from_f64(value as f64)
+    }
+
+    #[inline]
+    pub fn to_f32(self) -> f32 {
+        self.to_f64() as f32
+    }
+
+    #[inline]
+    pub const fn abs(self) -> Self {
+        Self(self.0.saturating_abs())
+    }
+
+    /// The square root rounded down, negative values give zero
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Self::ZERO;
+        }
+        Self(((self.0 as u128) << Self::FRACTIONAL_BITS).isqrt() as i64)
+    }
+
+    /// The sine and cosine of `self` in radians, accurate to a few [`Fixed::EPSILON`]s
+    pub fn sin_cos(self) -> (Self, Self) {
+        // π/2 with 62 fractional bits, so that reducing large angles stays accurate
+        const FRAC_PI_2_BITS: i128 = 7244019458077122842;
+        const EXTRA_BITS: u32 = 62 - Fixed::FRACTIONAL_BITS;
+
+        // reduce to `[-π/4, π/4]` plus a number of quarter turns
+        let angle = (self.0 as i128) << EXTRA_BITS;
+        let quarter_turns = (angle + FRAC_PI_2_BITS / 2).div_euclid(FRAC_PI_2_BITS);
+        let x = Self(((angle - quarter_turns * FRAC_PI_2_BITS) >> EXTRA_BITS) as i64);
+
+        // the Taylor series are accurate enough in this range with a few terms
+        let x2 = x * x;
+        let mut sin = Self::ONE;
+        for k in (1..=6).rev() {
+            sin = Self::ONE - x2 * sin / Self::from_int(2 * k * (2 * k + 1));
+        }
+        let sin = x * sin;
+        let mut cos = Self::ONE;
+        for k in (1..=7).rev() {
+            cos = Self::ONE - x2 * cos / Self::from_int((2 * k - 1) * 2 * k);
+        }
+
+        match quarter_turns.rem_euclid(4) {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+
+    #[inline]
+    pub fn sin(self) -> Self {
+        self.sin_cos().0
+    }
+
+    #[inline]
+    pub fn cos(self) -> Self {
+        self.sin_cos().1
+    }
+
+    /// The angle in radians from the positive x axis to the point `(other, self)`, in `[-π, π]`
+    pub fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self.abs(), other.abs());
+        if x == Self::ZERO && y == Self::ZERO {
+            return Self::ZERO;
+        }
+        let angle = if y <= x {
+            (y / x).atan_unit()
+        } else {
+            Self::FRAC_PI_2 - (x / y).atan_unit()
+        };
+        let angle = if other.0 < 0 { Self::PI - angle } else { angle };
+        if self.0 < 0 {
+            -angle
+        } else {
+            angle
+        }
+    }
+
+    /// The arctangent of a value in `[0, 1]`
+    fn atan_unit(self) -> Self {
+        // tan(π/8), above it `atan(x) = π/4 + atan((x - 1) / (x + 1))` brings the value below it
+        const TAN_FRAC_PI_8: Fixed = Fixed(1779033704);
+
+        let (offset, x) = if self > TAN_FRAC_PI_8 {
+            (Self::FRAC_PI_4, (self - Self::ONE) / (self + Self::ONE))
+        } else {
+            (Self::ZERO, self)
+        };
+        let x2 = x * x;
+        let mut sum = Self::ONE / Self::from_int(25);
+        for k in (0..12).rev() {
+            sum = Self::ONE / Self::from_int(2 * k + 1) - x2 * sum;
+        }
+        offset + x * sum
+    }
+}
+
+impl From<i32> for Fixed {
+    #[inline]
+    fn from(value: i32) -> Self {
+        Self::from_int(value)
+    }
+}
+
+/// Rounds `numerator / denominator` to the nearest integer, with halves rounded up
+#[inline]
+fn div_round(numerator: u128, denominator: u128) -> u128 {
+    (numerator + denominator / 2) / denominator
+}
+
+impl fmt::Display for Fixed {
+    /// Writes the shortest decimal that parses back to the same value, or rounds to the precision
+    /// if one is given
+    ///
+    /// Every value has an exact decimal with at most 32 decimals, so longer precisions end in zeros
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const ONE: u64 = 1 << Fixed::FRACTIONAL_BITS;
+
+        let magnitude = self.0.unsigned_abs();
+        let mut integer = magnitude >> Self::FRACTIONAL_BITS;
+        let fraction = magnitude & (ONE - 1);
+        let decimals = f.precision().unwrap_or_else(|| {
+            let (fraction, one) = (fraction as u128, ONE as u128);
+            (0..=10)
+                .find(|&decimals| {
+                    let scale = 10u128.pow(decimals);
+                    div_round(div_round(fraction * scale, one) * one, scale) == fraction
+                })
+                .unwrap() as usize
+        });
+
+        // the exact decimals one at a time, which run out after the 32nd
+        let mut digits = Vec::with_capacity(decimals.min(Self::FRACTIONAL_BITS as usize));
+        let mut rest = fraction;
+        while digits.len() < decimals && rest != 0 {
+            rest *= 10;
+            digits.push((rest >> Self::FRACTIONAL_BITS) as u8);
+            rest &= ONE - 1;
+        }
+        // halves round up, carrying through trailing nines into the integer
+        if rest >= ONE / 2 {
+            match digits.iter().rposition(|&digit| digit != 9) {
+                Some(last) => {
+                    digits[last] += 1;
+                    digits.truncate(last + 1);
+                }
+                None => {
+                    digits.clear();
+                    integer += 1;
+                }
+            }
+        }
+
+        let is_zero = integer == 0 && digits.iter().all(|&digit| digit == 0);
+        let sign = if self.0 < 0 && !is_zero { "-" } else { "" };
+        write!(f, "{sign}{integer}")?;
+        if decimals == 0 {
+            return Ok(());
+        }
+        let digits: String = digits
+            .iter()
+            .map(|&digit| char::from(b'0' + digit))
+            .collect();
+        write!(f, ".{digits:0<decimals$}")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFixedError;
+
+impl fmt::Display for ParseFixedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid fixed-point number")
+    }
+}
+
+impl std::error::Error for ParseFixedError {}
+
+impl FromStr for Fixed {
+    type Err = ParseFixedError;
+
+    /// Parses a decimal like `-12.375`, rounding to the nearest representable value
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        // the halfway points between values are multiples of 2^-33, which have 33 decimals, so
+        // cutting off the digits after those can't move the input past one
+        const MAX_DECIMALS: usize = Fixed::FRACTIONAL_BITS as usize + 1;
+
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let is_digits = |text: &str| text.bytes().all(|byte| byte.is_ascii_digit());
+        if integer.is_empty() && fraction.is_empty() || !is_digits(integer) || !is_digits(fraction)
+        {
+            return Err(ParseFixedError);
+        }
+
+        let integer = if integer.is_empty() {
+            0
+        } else {
+            integer.parse::<u128>().map_err(|_| ParseFixedError)?
+        };
+        // doubling the decimals carries the bits of the fraction out of the front one at a time,
+        // one more than fits to round with
+        let mut digits: Vec<u8> = fraction
+            .bytes()
+            .take(MAX_DECIMALS)
+            .map(|byte| byte - b'0')
+            .collect();
+        let mut bits = 0u128;
+        for _ in 0..=Self::FRACTIONAL_BITS {
+            let mut carry = 0;
+            for digit in digits.iter_mut().rev() {
+                let doubled = *digit * 2 + carry;
+                (*digit, carry) = (doubled % 10, doubled / 10);
+            }
+            bits = bits << 1 | carry as u128;
+        }
+        // halves round up
+        let fraction = (bits + 1) >> 1;
+
+        let magnitude = integer
+            .checked_shl(Self::FRACTIONAL_BITS)
+            .filter(|&shifted| shifted >> Self::FRACTIONAL_BITS == integer)
+            .and_then(|shifted| shifted.checked_add(fraction))
+            .ok_or(ParseFixedError)?;
+        let bits = if negative {
+            i64::try_from(-(magnitude as i128))
+        } else {
+            i64::try_from(magnitude)
+        };
+        bits.map(Self).map_err(|_| ParseFixedError)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self(self.0.saturating_neg())
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn add(self, rhs: Fixed) -> Self::Output {
+        Self(self.0.saturating_add(rhs.0))
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn sub(self, rhs: Fixed) -> Self::Output {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn mul(self, rhs: Fixed) -> Self::Output {
+        Self::saturating_from_wide((self.0 as i128 * rhs.0 as i128) >> Self::FRACTIONAL_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    #[inline]
+    fn div(self, rhs: Fixed) -> Self::Output {
+        if rhs.0 == 0 {
+            return match self.0.signum() {
+                1 => Self::MAX,
+                -1 => Self::MIN,
+                _ => Self::ZERO,
+            };
+        }
+        Self::saturating_from_wide(((self.0 as i128) << Self::FRACTIONAL_BITS) / rhs.0 as i128)
+    }
+}
+
+#[test]
+fn arithmetic() {
+    let a = Fixed::from_f64(2.5);
+    let b = Fixed::from_int(-4);
+    assert_eq!((a + b).to_f64(), -1.5);
+    assert_eq!((a - b).to_f64(), 6.5);
+    assert_eq!((a * b).to_f64(), -10.0);
+    // -1.6 truncated towards zero
+    assert_eq!(b / a, Fixed::from_bits(-6871947673));
+    assert_eq!((-a).to_int(), -3);
+    assert_eq!(Fixed::HALF * Fixed::from_int(3), Fixed::from_f32(1.5));
+
+    // dividing by zero goes to the end of the range, like a float going to infinity
+    assert_eq!(a / Fixed::ZERO, Fixed::MAX);
+    assert_eq!(b / Fixed::ZERO, Fixed::MIN);
+    assert_eq!(Fixed::ZERO / Fixed::ZERO, Fixed::ZERO);
+    assert_eq!(Fixed::EPSILON / Fixed::EPSILON, Fixed::ONE);
+
+    // and so does anything else that doesn't fit, in debug and release builds alike
+    let big = Fixed::from_int(i32::MAX);
+    assert_eq!(Fixed::MAX + Fixed::EPSILON, Fixed::MAX);
+    assert_eq!(Fixed::MIN - Fixed::EPSILON, Fixed::MIN);
+    assert_eq!(-Fixed::MIN, Fixed::MAX);
+    assert_eq!(Fixed::MIN.abs(), Fixed::MAX);
+    assert_eq!(big * big, Fixed::MAX);
+    assert_eq!(big * -big, Fixed::MIN);
+    assert_eq!(big / Fixed::EPSILON, Fixed::MAX);
+    assert_eq!(-big / Fixed::EPSILON, Fixed::MIN);
+    assert_eq!(Fixed::MIN / -Fixed::ONE, Fixed::MAX);
+}
+
+#[test]
+fn text() {
+    for value in [0.0, 1.5, -2.25, 0.1, -1234.5678, 3.0e-10] {
+        let fixed = Fixed::from_f64(value);
+        assert_eq!(fixed.to_string().parse::<Fixed>(), Ok(fixed));
+    }
+    assert_eq!(Fixed::from_f64(-2.25).to_string(), "-2.25");
+    assert_eq!(Fixed::from_int(7).to_string(), "7");
+    assert_eq!(format!("{:.3}", Fixed::from_f64(1.0 / 3.0)), "0.333");
+    assert_eq!(
+        format!("{:.30}", Fixed::from_f64(-2.25)),
+        "-2.250000000000000000000000000000"
+    );
+    assert_eq!(
+        format!("{:.30}", Fixed::MAX),
+        "2147483647.999999999767169356346130371094"
+    );
+    assert_eq!(
+        format!("{:.40}", Fixed::MAX),
+        "2147483647.9999999997671693563461303710937500000000"
+    );
+    assert_eq!(format!("{:.2}", Fixed::from_f64(-0.999)), "-1.00");
+    assert_eq!(format!("{:.2}", Fixed::from_f64(-0.001)), "0.00");
+    assert_eq!(format!("{:.0}", Fixed::from_f64(2.5)), "3");
+    assert_eq!("0.1".parse::<Fixed>().unwrap().to_string(), "0.1");
+    assert_eq!(Fixed::MIN.to_string().parse::<Fixed>(), Ok(Fixed::MIN));
+    assert_eq!(Fixed::MAX.to_string().parse::<Fixed>(), Ok(Fixed::MAX));
+    assert_eq!("2147483648".parse::<Fixed>(), Err(ParseFixedError));
+    assert_eq!("1.2.3".parse::<Fixed>(), Err(ParseFixedError));
+    assert_eq!(".".parse::<Fixed>(), Err(ParseFixedError));
+
+    // halfway between zero and the smallest value, which needs every one of its 33 decimals
+    let half_epsilon = "0.000000000116415321826934814453125";
+    assert_eq!(half_epsilon.parse(), Ok(Fixed::EPSILON));
+    assert_eq!(format!("{half_epsilon}0001").parse(), Ok(Fixed::EPSILON));
+    assert_eq!(
+        "0.000000000116415321826934814453124999".parse(),
+        Ok(Fixed::ZERO)
+    );
+    assert_eq!(
+        "0.99999999999999999999999999999999999".parse(),
+        Ok(Fixed::ONE)
+    );
+}
+
+#[test]
+fn functions() {
+    let assert_close = |a: Fixed, b: f64| {
+        assert!((a.to_f64() - b).abs() < 1e-8, "{a} != {b}");
+    };
+
+    assert_eq!(Fixed::from_int(4).sqrt(), Fixed::from_int(2));
+    assert_eq!(Fixed::from_f64(0.25).sqrt(), Fixed::HALF);
+    assert_eq!(Fixed::from_int(-1).sqrt(), Fixed::ZERO);
+    assert_close(Fixed::from_int(2).sqrt(), std::f64::consts::SQRT_2);
+    assert_close(Fixed::MAX.sqrt(), Fixed::MAX.to_f64().sqrt());
+
+    for i in -200..=200 {
+        let angle = Fixed::from_f64(i as f64 * 0.0731);
+        let (sin, cos) = angle.sin_cos();
+        assert_close(sin, angle.to_f64().sin());
+        assert_close(cos, angle.to_f64().cos());
+
+        let (y, x) = (
+            Fixed::from_f64(i as f64 * 0.37),
+            Fixed::from_f64(i as f64 * -0.11 + 3.0),
+        );
+        assert_close(y.atan2(x), y.to_f64().atan2(x.to_f64()));
+        assert_close(x.atan2(y), x.to_f64().atan2(y.to_f64()));
+    }
+    assert_close(Fixed::from_int(1000).sin(), 1000f64.sin());
+    assert_eq!(Fixed::ZERO.sin_cos(), (Fixed::ZERO, Fixed::ONE));
+    assert_eq!(Fixed::ZERO.atan2(Fixed::ZERO), Fixed::ZERO);
+    assert_eq!(Fixed::ZERO.atan2(-Fixed::ONE), Fixed::PI);
+    assert_close(Fixed::PI, std::f64::consts::PI);
+    assert_close(Fixed::TAU, std::f64::consts::TAU);
+    assert_close(Fixed::FRAC_PI_2, std::f64::consts::FRAC_PI_2);
+}
@@ -0,0 +1,331 @@
+//! Predicates and polygon queries built on [`Point`] and [`Line`]
+//!
+//! Polygons are slices of points in order, either winding works unless stated otherwise, and
+//! every point is expected to be finite (not ideal)
+
+use crate::multivector::{Line, Point, Real};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    Collinear,
+    CounterClockwise,
+}
+
+/// Whether `c` is to the left (counter-clockwise) or right (clockwise) of the line from `a` to `b`
+///
+/// There is no tolerance, so only exactly collinear points are [`Orientation::Collinear`]. For
+/// [`Fixed`](crate::fixed::Fixed) this is exact for any coordinates, as it doesn't round or
+/// saturate, though the coordinates of points whose weight isn't one are rounded first
+#[inline]
+pub fn orientation<T: Real>(a: Point<T>, b: Point<T>, c: Point<T>) -> Orientation {
+    match T::cross_sign((a.x(), a.y()), (b.x(), b.y()), (c.x(), c.y())) {
+        Ordering::Greater => Orientation::CounterClockwise,
+        Ordering::Less => Orientation::Clockwise,
+        Ordering::Equal => Orientation::Collinear,
+    }
+}
+
+/// The area of `polygon`, positive if it winds counter-clockwise
+pub fn signed_area<T: Real>(polygon: &[Point<T>]) -> T {
+    let Some(&first) = polygon.first() else {
+        return T::ZERO;
+    };
+    let mut area = T::ZERO;
+    for edge in polygon.windows(2) {
+        area = area + triangle_area(first, edge[0], edge[1]);
+    }
+    area
+}
+
+/// The center of mass of `polygon`, this is an ideal point if its area is zero
+pub fn centroid<T: Real>(polygon: &[Point<T>]) -> Point<T> {
+    let Some(&first) = polygon.first() else {
+        return Point::direction(T::ZERO, T::ZERO);
+    };
+    // the area-weighted sum of the centroids of the triangles that fan out from the first vertex,
+    // where the sum of a triangle's vertices is three times its centroid
+    let (mut x, mut y, mut total) = (T::ZERO, T::ZERO, T::ZERO);
+    for edge in polygon.windows(2) {
+        let (b, c) = (edge[0], edge[1]);
+        let area = triangle_area(first, b, c);
+        x = x + (first.x() + b.x() + c.x()) * area;
+        y = y + (first.y() + b.y() + c.y()) * area;
+        total = total + area;
+    }
+    if total == T::ZERO {
+        Point::direction(x, y)
+    } else {
+        let weight = total + total + total;
+        Point::new(x / weight, y / weight)
+    }
+}
+
+#[inline]
+fn triangle_area<T: Real>(a: Point<T>, b: Point<T>, c: Point<T>) -> T {
+    T::HALF * (a & b & c).s / (a.e12 * b.e12 * c.e12)
+}
+
+/// The edges of `polygon` including the one from the last point back to the first
+fn edges<T: Real>(polygon: &[Point<T>]) -> impl Iterator<Item = (Point<T>, Point<T>)> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// Whether `point` is inside or on the edge of the convex `polygon`
+///
+/// A polygon whose points all lie on one line, including one with fewer than 3 points, has no
+/// inside, so it contains nothing
+pub fn contains_convex<T: Real>(polygon: &[Point<T>], point: Point<T>) -> bool {
+    let (mut left, mut right) = (false, false);
+    for (a, b) in edges(polygon) {
+        match orientation(a, b, point) {
+            Orientation::CounterClockwise => left = true,
+            Orientation::Clockwise => right = true,
+            Orientation::Collinear => {}
+        }
+        if left && right {
+            return false;
+        }
+    }
+    // every edge is collinear with `point` only when the polygon is a line through it
+    left || right
+}
+
+/// How many times `polygon` winds counter-clockwise around `point`, negative for clockwise
+///
+/// Points exactly on an edge may be counted either way
+pub fn winding_number<T: Real>(polygon: &[Point<T>], point: Point<T>) -> i32 {
+    let y = point.y();
+    let mut winding = 0;
+    for (a, b) in edges(polygon) {
+        if a.y() <= y {
+            if b.y() > y && orientation(a, b, point) == Orientation::CounterClockwise {
+                winding += 1;
+            }
+        } else if b.y() <= y && orientation(a, b, point) == Orientation::Clockwise {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// Whether `point` is inside `polygon`, which may be concave or self-intersecting, using the
+/// non-zero winding rule
+#[inline]
+pub fn contains<T: Real>(polygon: &[Point<T>], point: Point<T>) -> bool {
+    winding_number(polygon, point) != 0
+}
+
+/// Where the segment from `a` to `b` crosses the segment from `c` to `d`, including touching ends
+///
+/// Overlapping collinear segments give the end of the overlap closest to `a`
+pub fn segment_intersection<T: Real>(
+    a: Point<T>,
+    b: Point<T>,
+    c: Point<T>,
+    d: Point<T>,
+) -> Option<Point<T>> {
+    let (abc, abd) = (orientation(a, b, c), orientation(a, b, d));
+    let (cda, cdb) = (orientation(c, d, a), orientation(c, d, b));
+    if abc != abd && cda != cdb && ![abc, abd, cda, cdb].contains(&Orientation::Collinear) {
+        let point = Line::through(a, b).intersection(Line::through(c, d));
+        return Some(Point::new(point.x(), point.y()));
+    }
+
+    // the segments can only touch or overlap, find the closest candidate to `a` on both
+    let mut closest: Option<(T, Point<T>)> = None;
+    for (point, on_segment) in [
+        (a, cda == Orientation::Collinear),
+        (b, cdb == Orientation::Collinear),
+        (c, abc == Orientation::Collinear),
+        (d, abd == Orientation::Collinear),
+    ] {
+        if !on_segment || !within(a, b, point) || !within(c, d, point) {
+            continue;
+        }
+        let distance = squared_distance(a, point);
+        if closest.is_none_or(|(closest, _)| distance < closest) {
+            closest = Some((distance, point));
+        }
+    }
+    closest.map(|(_, point)| point)
+}
+
+/// Whether `point`, which is collinear with `a` and `b`, lies between them
+#[inline]
+fn within<T: Real>(a: Point<T>, b: Point<T>, point: Point<T>) -> bool {
+    let between = |a: T, b: T, value: T| {
+        let (min, max) = if a < b { (a, b) } else { (b, a) };
+        min <= value && value <= max
+    };
+    between(a.x(), b.x(), point.x()) && between(a.y(), b.y(), point.y())
+}
+
+#[inline]
+fn squared_distance<T: Real>(a: Point<T>, b: Point<T>) -> T {
+    let (x, y) = (b.x() - a.x(), b.y() - a.y());
+    x * x + y * y
+}
+
+/// The point on the segment from `a` to `b` that is closest to `point`
+pub fn closest_point_on_segment<T: Real>(a: Point<T>, b: Point<T>, point: Point<T>) -> Point<T> {
+    let (x, y) = (b.x() - a.x(), b.y() - a.y());
+    let length = x * x + y * y;
+    if length == T::ZERO {
+        return Point::new(a.x(), a.y());
+    }
+    let t = ((point.x() - a.x()) * x + (point.y() - a.y()) * y) / length;
+    if t <= T::ZERO {
+        Point::new(a.x(), a.y())
+    } else if t >= T::ONE {
+        Point::new(b.x(), b.y())
+    } else {
+        Point::new(a.x() + x * t, a.y() + y * t)
+    }
+}
+
+#[test]
+fn predicates() {
+    use crate::fixed::Fixed;
+
+    let a: Point = Point::new(0.0, 0.0);
+    let b: Point = Point::new(2.0, 0.0);
+    assert_eq!(
+        orientation(a, b, Point::new(1.0, 1.0)),
+        Orientation::CounterClockwise
+    );
+    assert_eq!(
+        orientation(a, b, Point::new(1.0, -1.0)),
+        Orientation::Clockwise
+    );
+    assert_eq!(
+        orientation(a, b, Point::new(5.0, 0.0)),
+        Orientation::Collinear
+    );
+    // the weight doesn't change the answer
+    assert_eq!(
+        orientation(a * -2.0, b, Point::new(1.0, 1.0)),
+        Orientation::CounterClockwise
+    );
+
+    // exact for fixed-point, both for tiny triangles and for products that don't fit
+    let fixed = |x: f64, y: f64| Point::new(Fixed::from_f64(x), Fixed::from_f64(y));
+    let tiny = 2f64.powi(-20);
+    assert_eq!(
+        orientation(fixed(0.0, 0.0), fixed(tiny, 0.0), fixed(0.0, tiny)),
+        Orientation::CounterClockwise
+    );
+    let (far, epsilon) = (5.0e4, 2f64.powi(-32));
+    let (low, high) = (fixed(-far, -far), fixed(far, far));
+    assert_eq!(
+        orientation(low, high, fixed(far, far - epsilon)),
+        Orientation::Clockwise
+    );
+    assert_eq!(
+        orientation(low, high, fixed(far - epsilon, far)),
+        Orientation::CounterClockwise
+    );
+    assert_eq!(
+        orientation(low, high, fixed(0.0, 0.0)),
+        Orientation::Collinear
+    );
+
+    let cross = |x0, y0, x1, y1, x2, y2, x3, y3| {
+        segment_intersection(
+            Point::new(x0, y0),
+            Point::new(x1, y1),
+            Point::new(x2, y2),
+            Point::new(x3, y3),
+        )
+        .map(|point: Point| (point.x(), point.y()))
+    };
+    assert_eq!(
+        cross(0.0, 0.0, 2.0, 2.0, 0.0, 2.0, 2.0, 0.0),
+        Some((1.0, 1.0))
+    );
+    assert_eq!(cross(0.0, 0.0, 1.0, 1.0, 0.0, 2.0, 2.0, 3.0), None);
+    assert_eq!(
+        cross(0.0, 0.0, 2.0, 0.0, 2.0, 0.0, 3.0, 5.0),
+        Some((2.0, 0.0))
+    );
+    assert_eq!(
+        cross(0.0, 0.0, 2.0, 0.0, 1.0, 0.0, 1.0, 3.0),
+        Some((1.0, 0.0))
+    );
+    // `c` touching the middle of `ab` doesn't make `a` part of `cd`
+    assert_eq!(
+        cross(0.0, 0.0, 2.0, 0.0, 1.0, 0.0, -1.0, 2.0),
+        Some((1.0, 0.0))
+    );
+    assert_eq!(
+        cross(0.0, 0.0, 4.0, 0.0, 3.0, 0.0, 1.0, 0.0),
+        Some((1.0, 0.0))
+    );
+    assert_eq!(cross(0.0, 0.0, 1.0, 0.0, 2.0, 0.0, 3.0, 0.0), None);
+    assert_eq!(cross(0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0), None);
+
+    let closest = |x, y| {
+        let point: Point = closest_point_on_segment(a, b, Point::new(x, y));
+        (point.x(), point.y())
+    };
+    assert_eq!(closest(1.0, 3.0), (1.0, 0.0));
+    assert_eq!(closest(-1.0, 1.0), (0.0, 0.0));
+    assert_eq!(closest(5.0, -1.0), (2.0, 0.0));
+}
+
+#[test]
+fn polygons() {
+    let square: [Point; 4] = [
+        Point::new(0.0, 0.0),
+        Point::new(2.0, 0.0),
+        Point::new(2.0, 2.0),
+        Point::new(0.0, 2.0),
+    ];
+    assert_eq!(signed_area(&square), 4.0);
+    let mut clockwise = square;
+    clockwise.reverse();
+    assert_eq!(signed_area(&clockwise), -4.0);
+    let center = centroid(&clockwise);
+    assert_eq!((center.x(), center.y()), (1.0, 1.0));
+
+    assert!(contains_convex(&square, Point::new(1.0, 1.0)));
+    assert!(contains_convex(&clockwise, Point::new(2.0, 1.0)));
+    assert!(!contains_convex(&square, Point::new(3.0, 1.0)));
+    assert!(!contains_convex(&[], Point::new(0.0, 0.0)));
+    assert!(!contains_convex(&square[..1], Point::new(5.0, 5.0)));
+    assert!(!contains_convex(&square[..2], Point::new(1.0, 0.0)));
+    assert!(!contains_convex(&square[..2], Point::new(-3.0, 0.0)));
+    let line: [Point; 3] = [
+        Point::new(0.0, 0.0),
+        Point::new(1.0, 0.0),
+        Point::new(2.0, 0.0),
+    ];
+    assert!(!contains_convex(&line, Point::new(5.0, 0.0)));
+    assert!(!contains_convex(&line, Point::new(1.0, 0.0)));
+    assert!(!contains_convex(&line, Point::new(1.0, 1.0)));
+    // but the corners and edges of a proper polygon are still inside
+    assert!(contains_convex(&square, Point::new(0.0, 0.0)));
+    assert!(contains_convex(&square, Point::new(1.0, 0.0)));
+
+    // an L shape, where the convex test would be wrong
+    let l_shape: [Point; 6] = [
+        Point::new(0.0, 0.0),
+        Point::new(3.0, 0.0),
+        Point::new(3.0, 1.0),
+        Point::new(1.0, 1.0),
+        Point::new(1.0, 3.0),
+        Point::new(0.0, 3.0),
+    ];
+    assert_eq!(signed_area(&l_shape), 5.0);
+    let center = centroid(&l_shape);
+    assert!((center.x() - 1.1).abs() < 1e-6 && (center.y() - 1.1).abs() < 1e-6);
+    assert!(contains(&l_shape, Point::new(0.5, 2.5)));
+    assert!(contains(&l_shape, Point::new(2.5, 0.5)));
+    assert!(!contains(&l_shape, Point::new(2.0, 2.0)));
+    assert_eq!(winding_number(&l_shape, Point::new(0.5, 0.5)), 1);
+    assert_eq!(winding_number(&clockwise, Point::new(0.5, 0.5)), -1);
+}
@@ -1,12 +1,27 @@
 #![allow(clippy::type_complexity)]
 #![deny(rust_2018_idioms)]
 
+mod convert;
+pub mod fixed;
+pub mod geometry;
 pub mod multivector;
+pub mod multivector3d;
+#[cfg(test)]
+mod reference_algebra;
 pub mod renderer;
+pub mod simd;
+pub mod time;
 pub mod window;
 
-use bevy::{app::PluginGroupBuilder, prelude::*};
+use bevy::{
+    app::PluginGroupBuilder,
+    ecs::{entity::Entities, schedule::ScheduleLabel},
+    math::{Affine2, Mat2, Vec2},
+    prelude::*,
+};
+use enum_map::Enum;
 use renderer::RendererPlugin;
+use time::{FixedPostUpdate, TimePlugin};
 use window::WindowPlugin;
 
 pub struct GamePlugins;
@@ -16,70 +31,248 @@ impl PluginGroup for GamePlugins {
         PluginGroupBuilder::start::<Self>()
             .add(WindowPlugin)
             .add(RendererPlugin)
+            .add(TimePlugin)
             .add(TransformPlugin)
     }
 }
 
-// TODO: switch to motors
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub struct Transform {
     pub x: f32,
     pub y: f32,
+    /// Counter-clockwise, in radians
+    pub rotation: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
 }
 
 impl Transform {
+    pub const IDENTITY: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        rotation: 0.0,
+        scale_x: 1.0,
+        scale_y: 1.0,
+    };
+
     #[inline]
-    pub fn apply(self, other: Transform) -> Transform {
-        Transform {
-            x: self.x + other.x,
-            y: self.y + other.y,
+    pub const fn from_xy(x: f32, y: f32) -> Self {
+        Self {
+            x,
+            y,
+            ..Self::IDENTITY
         }
     }
+
+    #[inline]
+    pub const fn with_rotation(self, rotation: f32) -> Self {
+        Self { rotation, ..self }
+    }
+
+    #[inline]
+    pub const fn with_scale(self, scale: f32) -> Self {
+        self.with_scale_xy(scale, scale)
+    }
+
+    #[inline]
+    pub const fn with_scale_xy(self, scale_x: f32, scale_y: f32) -> Self {
+        Self {
+            scale_x,
+            scale_y,
+            ..self
+        }
+    }
+
+    /// Scales, then rotates, then moves
+    #[inline]
+    pub fn to_affine(self) -> Affine2 {
+        Affine2::from_scale_angle_translation(
+            Vec2::new(self.scale_x, self.scale_y),
+            self.rotation,
+            Vec2::new(self.x, self.y),
+        )
+    }
+
+    /// Places `self` inside `parent`, so that it moves, rotates and scales along with it
+    #[inline]
+    pub fn apply(self, parent: &GlobalTransform) -> GlobalTransform {
+        GlobalTransform(parent.0 * self.to_affine())
+    }
 }
 
-#[derive(Component, Clone, Copy)]
-pub struct GlobalTransform(pub(crate) Transform);
+/// The transform relative to the world, after applying the transforms of every parent
+///
+/// This is a full affine transform rather than a [`Transform`], as a parent with a non-uniform
+/// scale can skew its rotated children
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GlobalTransform(pub(crate) Affine2);
 
 impl GlobalTransform {
     #[inline]
-    pub fn transform(&self) -> &Transform {
+    pub fn affine(&self) -> &Affine2 {
         &self.0
     }
+
+    #[inline]
+    pub fn translation(&self) -> Vec2 {
+        self.0.translation
+    }
+
+    #[inline]
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.0.transform_point2(point)
+    }
+}
+
+impl From<Transform> for GlobalTransform {
+    #[inline]
+    fn from(transform: Transform) -> Self {
+        Self(transform.to_affine())
+    }
+}
+
+/// The [`GlobalTransform`] before the last fixed step, which the renderer blends from so that
+/// movement in [`FixedUpdate`] looks smooth at any frame rate
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub(crate) struct PreviousGlobalTransform(pub(crate) Affine2);
+
+impl PreviousGlobalTransform {
+    /// Blends towards `current` by `alpha`, turning the shorter way round, so a rotation in one
+    /// step is accurate up to half a turn
+    ///
+    /// The rotation and the rest of the matrix are blended separately, so that a rotating shape
+    /// keeps its size and shape partway through a step
+    pub(crate) fn interpolate(&self, current: &GlobalTransform, alpha: f32) -> GlobalTransform {
+        use std::f32::consts::{PI, TAU};
+
+        // the angle of the x axis, and what is left once that is undone, which has no rotation
+        let split = |matrix2: Mat2| {
+            let angle = matrix2.x_axis.y.atan2(matrix2.x_axis.x);
+            (angle, Mat2::from_angle(-angle) * matrix2)
+        };
+        let (previous_angle, previous_rest) = split(self.0.matrix2);
+        let (current_angle, current_rest) = split(current.0.matrix2);
+        let turn = (current_angle - previous_angle + PI).rem_euclid(TAU) - PI;
+        GlobalTransform(Affine2 {
+            matrix2: Mat2::from_angle(previous_angle + turn * alpha)
+                * (previous_rest * (1.0 - alpha) + current_rest * alpha),
+            translation: self.0.translation.lerp(current.0.translation, alpha),
+        })
+    }
+}
+
+/// A problem with the hierarchy found while propagating transforms
+///
+/// Each problem is reported once, when it appears, and the affected entities keep being updated
+/// as well as possible rather than stalling the schedule
+///
+/// The hierarchy is checked every time transforms are propagated, so a problem caused inside
+/// [`FixedUpdate`] is reported in the [`FixedPostUpdate`] of that step rather than in
+/// [`PostUpdate`], which makes no difference to systems reading it with an [`EventReader`]
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum HierarchyError {
+    /// Following [`Parent`] from the first entity leads back to it through the others
+    ///
+    /// The entities of a cycle and their descendants are not updated until it is broken
+    Cycle(Vec<Entity>),
+    /// The [`Parent`] of `entity` was despawned or has no [`Transform`]
+    ///
+    /// `entity` is treated as a root until it is given a new parent
+    DanglingParent { entity: Entity, parent: Entity },
 }
 
 struct TransformPlugin;
 
+/// Brings every [`GlobalTransform`] up to date and checks the hierarchy for [`HierarchyError`]s,
+/// after every fixed step and once more every frame
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+struct PropagateTransforms;
+
 impl Plugin for TransformPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            PostUpdate,
-            (
-                remove_global_transforms,
-                update_global_transforms,
-                add_global_transforms,
+        app.add_event::<HierarchyError>()
+            .add_systems(
+                PropagateTransforms,
+                (
+                    remove_global_transforms,
+                    update_global_transforms,
+                    add_global_transforms,
+                    sync_previous_global_transforms,
+                )
+                    .chain(),
             )
-                .chain(),
-        );
+            .add_systems(PostUpdate, propagate_transforms)
+            .add_systems(
+                FixedPostUpdate,
+                (save_previous_global_transforms, propagate_transforms).chain(),
+            );
+    }
+}
+
+fn propagate_transforms(world: &mut World) {
+    world.run_schedule(PropagateTransforms);
+}
+
+fn save_previous_global_transforms(
+    mut global_transforms: Query<'_, '_, (&GlobalTransform, &mut PreviousGlobalTransform)>,
+) {
+    for (global_transform, mut previous_global_transform) in &mut global_transforms {
+        previous_global_transform.0 = global_transform.0;
+    }
+}
+
+/// Snaps entities moved outside of a fixed step straight to their new transform, as there is no
+/// previous step to blend from
+fn sync_previous_global_transforms(
+    time: Res<'_, time::Time>,
+    mut global_transforms: Query<
+        '_,
+        '_,
+        (&GlobalTransform, &mut PreviousGlobalTransform),
+        Changed<GlobalTransform>,
+    >,
+) {
+    if time.in_fixed_step() {
+        return;
+    }
+    for (global_transform, mut previous_global_transform) in &mut global_transforms {
+        previous_global_transform.0 = global_transform.0;
     }
 }
 
 fn add_global_transforms(
     mut commands: Commands<'_, '_>,
-    transforms: Query<'_, '_, (Entity, &Transform, Option<&Parent>), Without<GlobalTransform>>,
+    new_transforms: Query<'_, '_, (Entity, &Transform, Option<&Parent>), Without<GlobalTransform>>,
+    transforms: Query<'_, '_, (&Transform, Option<&Parent>)>,
 ) {
-    for (entity, &transform, mut maybe_parent) in &transforms {
-        let mut final_transform = transform;
+    for (entity, &transform, mut maybe_parent) in &new_transforms {
+        let mut final_affine = transform.to_affine();
+        let mut ancestors = vec![entity];
         while let Some(parent) = maybe_parent {
-            let Ok((_, &parent_transform, parent)) = transforms.get(parent.get()) else {
+            let parent = parent.get();
+            if ancestors.contains(&parent) {
+                // the cycle is reported by `update_global_transforms`, so just start as a root
+                final_affine = transform.to_affine();
+                break;
+            }
+            ancestors.push(parent);
+            let Ok((parent_transform, parent)) = transforms.get(parent) else {
                 break;
             };
-            final_transform = final_transform.apply(parent_transform);
+            final_affine = parent_transform.to_affine() * final_affine;
             maybe_parent = parent;
         }
-        commands
-            .get_entity(entity)
-            .unwrap()
-            .insert(GlobalTransform(transform));
+        commands.get_entity(entity).unwrap().insert((
+            GlobalTransform(final_affine),
+            PreviousGlobalTransform(final_affine),
+        ));
     }
 }
 
@@ -96,32 +289,221 @@ fn remove_global_transforms(
         commands
             .get_entity(entity)
             .unwrap()
-            .remove::<GlobalTransform>();
+            .remove::<(GlobalTransform, PreviousGlobalTransform)>();
     }
 }
 
-// TODO: find some way to avoid updating transforms if nothing has changed
+/// Propagates transforms from the roots of the hierarchy down, skipping subtrees where neither a
+/// transform nor a parent has changed, with every root handled in parallel
+///
+/// Entities whose parent is missing are propagated as roots, and cycles are left alone, both
+/// reported as a [`HierarchyError`]
+#[allow(clippy::too_many_arguments)]
 fn update_global_transforms(
-    transforms: Query<'_, '_, (Ref<'_, Transform>, Option<&Parent>)>,
-    mut global_transforms: Query<'_, '_, (&mut GlobalTransform, Ref<'_, Transform>, Option<&Parent>)>,
+    mut roots: Query<
+        '_,
+        '_,
+        (
+            Entity,
+            Ref<'_, Transform>,
+            &mut GlobalTransform,
+            Option<&Children>,
+        ),
+        Without<Parent>,
+    >,
+    transforms: Query<
+        '_,
+        '_,
+        (Ref<'_, Transform>, &mut GlobalTransform, Option<&Children>),
+        With<Parent>,
+    >,
+    parents: Query<'_, '_, (Entity, Ref<'_, Parent>)>,
+    reparented: Query<'_, '_, Entity, Changed<Parent>>,
+    children: Query<'_, '_, &Children>,
+    with_transform: Query<'_, '_, (), With<Transform>>,
+    entities: &Entities,
+    mut orphaned: RemovedComponents<'_, '_, Parent>,
+    mut lost_transforms: RemovedComponents<'_, '_, Transform>,
+    mut lost_children: RemovedComponents<'_, '_, Children>,
+    mut orphaned_entities: Local<'_, Vec<Entity>>,
+    mut dangling_entities: Local<'_, Vec<(Entity, Entity)>>,
+    mut cycles: Local<'_, Vec<Vec<Entity>>>,
+    mut errors: EventWriter<'_, HierarchyError>,
 ) {
-    global_transforms.par_iter_mut().for_each_mut(
-        |(mut global_transform, transform, mut maybe_parent)| {
-            let mut any_transform_changed = transform.is_changed();
-            let mut final_transform = *transform;
-            while let Some(parent) = maybe_parent {
-                let Ok((parent_transform, parent)) = transforms.get(parent.get()) else {
-                    break;
-                };
-                any_transform_changed |= parent_transform.is_changed();
-                final_transform = final_transform.apply(*parent_transform);
-                maybe_parent = parent;
+    orphaned_entities.clear();
+    orphaned_entities.extend(orphaned.iter());
+    orphaned_entities.sort_unstable();
+
+    // the problems found before are kept while they last, so each one is only reported once
+    let is_dangling = |entity: Entity| {
+        let (_, parent) = parents.get(entity).ok()?;
+        (with_transform.contains(entity) && !with_transform.contains(parent.get()))
+            .then(|| parent.get())
+    };
+    dangling_entities.retain(|&(entity, parent)| is_dangling(entity) == Some(parent));
+    cycles.retain(|cycle| find_cycle(cycle[0], &parents).as_ref() == Some(cycle));
+
+    // a parent can only go missing for the entities that were given a new one, the children of
+    // entities that lost their transform, and the children of despawned entities, which have to be
+    // found by looking through every parent but only when something with children was despawned
+    let mut candidates: Vec<Entity> = reparented.iter().collect();
+    for entity in lost_transforms.iter() {
+        if let Ok(children) = children.get(entity) {
+            candidates.extend(children);
+        }
+    }
+    let mut despawned: Vec<Entity> = lost_children
+        .iter()
+        .filter(|&entity| !entities.contains(entity))
+        .collect();
+    if !despawned.is_empty() {
+        despawned.sort_unstable();
+        candidates.extend(
+            parents
+                .iter()
+                .filter(|(_, parent)| despawned.binary_search(&parent.get()).is_ok())
+                .map(|(entity, _)| entity),
+        );
+    }
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    let mut newly_dangling = Vec::new();
+    for &entity in &candidates {
+        let Some(parent) = is_dangling(entity) else {
+            continue;
+        };
+        if let Err(index) = dangling_entities.binary_search_by_key(&entity, |&(entity, _)| entity) {
+            warn!("{entity:?} has a missing parent {parent:?}");
+            errors.send(HierarchyError::DanglingParent { entity, parent });
+            dangling_entities.insert(index, (entity, parent));
+            newly_dangling.push(entity);
+        }
+    }
+
+    // a cycle can only be closed by changing a parent
+    for entity in &reparented {
+        if let Some(cycle) = find_cycle(entity, &parents) {
+            if !cycles.contains(&cycle) {
+                warn!("the parents of {cycle:?} form a cycle");
+                errors.send(HierarchyError::Cycle(cycle.clone()));
+                cycles.push(cycle);
+            }
+        }
+    }
+
+    for &(entity, _) in dangling_entities.iter() {
+        // SAFETY: the parent of a dangling entity has no transform, so it is not reached from a
+        // root or another dangling entity, and nothing runs in parallel yet
+        unsafe {
+            propagate_global_transform(
+                &GlobalTransform(Affine2::IDENTITY),
+                &transforms,
+                &parents,
+                entity,
+                newly_dangling.binary_search(&entity).is_ok(),
+            );
+        }
+    }
+
+    roots
+        .par_iter_mut()
+        .for_each_mut(|(entity, transform, mut global_transform, children)| {
+            let changed = transform.is_changed()
+                || global_transform.is_added()
+                || orphaned_entities.binary_search(&entity).is_ok();
+            if changed {
+                *global_transform = GlobalTransform::from(*transform);
             }
-            if any_transform_changed {
-                global_transform.0 = final_transform;
+
+            let Some(children) = children else {
+                return;
+            };
+            for (child, parent) in parents.iter_many(children) {
+                // children that don't agree with their parent are left to the hierarchy commands
+                if parent.get() != entity {
+                    continue;
+                }
+                // SAFETY: every entity has one parent and the check above makes sure that it is
+                // the one we came from, so every entity is only reached from one root, once
+                unsafe {
+                    propagate_global_transform(
+                        &global_transform,
+                        &transforms,
+                        &parents,
+                        child,
+                        changed || parent.is_changed(),
+                    );
+                }
             }
-        },
-    );
+        });
+}
+
+/// # Safety
+///
+/// `transforms` must not be fetched for `entity` or any of its descendants while this runs
+unsafe fn propagate_global_transform(
+    parent_global_transform: &GlobalTransform,
+    transforms: &Query<
+        '_,
+        '_,
+        (Ref<'_, Transform>, &mut GlobalTransform, Option<&Children>),
+        With<Parent>,
+    >,
+    parents: &Query<'_, '_, (Entity, Ref<'_, Parent>)>,
+    entity: Entity,
+    mut changed: bool,
+) {
+    // SAFETY: the caller makes sure that nothing else is accessing `entity`
+    let Ok((transform, mut global_transform, children)) =
+        (unsafe { transforms.get_unchecked(entity) })
+    else {
+        return;
+    };
+    changed |= transform.is_changed() || global_transform.is_added();
+    if changed {
+        *global_transform = transform.apply(parent_global_transform);
+    }
+
+    let Some(children) = children else {
+        return;
+    };
+    for (child, parent) in parents.iter_many(children) {
+        if parent.get() != entity {
+            continue;
+        }
+        // SAFETY: the caller makes sure that nothing else is accessing the descendants of
+        // `entity`, and the check above makes sure that `child` is only reached through `entity`
+        unsafe {
+            propagate_global_transform(
+                &global_transform,
+                transforms,
+                parents,
+                child,
+                changed || parent.is_changed(),
+            );
+        }
+    }
+}
+
+/// Follows [`Parent`] up from `entity` and returns the cycle it ends in, if any, starting from the
+/// smallest entity so that every member reports the same cycle
+fn find_cycle(
+    entity: Entity,
+    parents: &Query<'_, '_, (Entity, Ref<'_, Parent>)>,
+) -> Option<Vec<Entity>> {
+    let mut path = vec![entity];
+    while let Ok((_, parent)) = parents.get(*path.last().unwrap()) {
+        let parent = parent.get();
+        if let Some(start) = path.iter().position(|&ancestor| ancestor == parent) {
+            let mut cycle = path.split_off(start);
+            let smallest = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap();
+            cycle.rotate_left(smallest);
+            return Some(cycle);
+        }
+        path.push(parent);
+    }
+    None
 }
 
 #[derive(Component, Clone, Copy)]
@@ -135,14 +517,489 @@ pub struct Circle {
     pub radius: f32,
 }
 
-#[derive(Component, Clone, Copy)]
+/// How a translucent shape is combined with what is drawn behind it
+#[derive(Enum, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Covers what is behind by `alpha`
+    #[default]
+    Alpha,
+    /// Adds its color, scaled by `alpha`, for glows and light
+    Additive,
+    /// Multiplies what is behind by its color, scaled by `alpha`, for shadows and tints
+    Multiply,
+}
+
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
 pub struct Material {
     pub red: f32,
     pub green: f32,
     pub blue: f32,
+    pub alpha: f32,
+    pub blend_mode: BlendMode,
 }
 
+impl Material {
+    pub const WHITE: Self = Self::rgb(1.0, 1.0, 1.0);
+
+    #[inline]
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::rgba(red, green, blue, 1.0)
+    }
+
+    #[inline]
+    pub const fn rgba(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+            blend_mode: BlendMode::Alpha,
+        }
+    }
+
+    #[inline]
+    pub const fn with_alpha(self, alpha: f32) -> Self {
+        Self { alpha, ..self }
+    }
+
+    #[inline]
+    pub const fn with_blend_mode(self, blend_mode: BlendMode) -> Self {
+        Self { blend_mode, ..self }
+    }
+
+    /// Whether this completely hides what is behind it, so it can be drawn in any order
+    #[inline]
+    pub fn is_opaque(&self) -> bool {
+        self.blend_mode == BlendMode::Alpha && self.alpha >= 1.0
+    }
+}
+
+impl Default for Material {
+    #[inline]
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+/// A stroke along the inside of the edge of a [`Quad`] or [`Circle`], drawn over its [`Material`]
+///
+/// The width is in the same units as the size of the shape, so it scales with the transform, but
+/// the stroke is never drawn thinner than a pixel. A hollow shape is an outline on a fully
+/// transparent material.
+///
+/// The width is measured before the transform, so on a shape scaled more along one axis than the
+/// other, the sides facing along that axis get a thicker stroke.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct Outline {
+    pub width: f32,
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    pub alpha: f32,
+}
+
+impl Outline {
+    #[inline]
+    pub const fn rgb(width: f32, red: f32, green: f32, blue: f32) -> Self {
+        Self::rgba(width, red, green, blue, 1.0)
+    }
+
+    #[inline]
+    pub const fn rgba(width: f32, red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            width,
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    #[inline]
+    pub fn is_opaque(&self) -> bool {
+        self.alpha >= 1.0
+    }
+}
+
+/// Where a shape is stacked, with higher layers drawn in front of lower ones
+///
+/// Shapes without a layer are on layer 0, and shapes on the same layer overlap in an unspecified
+/// order
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Layer(pub i16);
+
 #[derive(Component, Clone, Copy)]
 pub struct Camera {
     pub vertical_height: f32,
 }
+
+#[test]
+fn transform_composition() {
+    let parent = GlobalTransform::from(
+        Transform::from_xy(1.0, 2.0)
+            .with_rotation(std::f32::consts::FRAC_PI_2)
+            .with_scale(2.0),
+    );
+    let child = Transform::from_xy(1.0, 0.0).with_rotation(0.25);
+    let global = child.apply(&parent);
+    assert!(global.translation().abs_diff_eq(Vec2::new(1.0, 4.0), 1e-6));
+    // the child's own x axis is rotated by both and scaled by the parent
+    let x_axis = global.affine().matrix2.x_axis;
+    let angle = std::f32::consts::FRAC_PI_2 + 0.25;
+    assert!(x_axis.abs_diff_eq(Vec2::new(angle.cos(), angle.sin()) * 2.0, 1e-6));
+
+    // a non-uniform scale skews rotated children
+    let parent = GlobalTransform::from(Transform::IDENTITY.with_scale_xy(2.0, 1.0));
+    let global = Transform::IDENTITY
+        .with_rotation(std::f32::consts::FRAC_PI_4)
+        .apply(&parent);
+    let corner = global.transform_point(Vec2::new(1.0, 0.0));
+    let half_sqrt_2 = std::f32::consts::FRAC_1_SQRT_2;
+    assert!(corner.abs_diff_eq(Vec2::new(2.0 * half_sqrt_2, half_sqrt_2), 1e-6));
+}
+
+#[test]
+fn transform_propagation() {
+    let mut world = World::new();
+    world.init_resource::<Events<HierarchyError>>();
+    let mut schedule = Schedule::new();
+    schedule.add_systems(update_global_transforms);
+
+    let spawn = |world: &mut World, transform: Transform| {
+        world
+            .spawn((transform, GlobalTransform::from(Transform::IDENTITY)))
+            .id()
+    };
+    let root = spawn(
+        &mut world,
+        Transform::from_xy(1.0, 0.0).with_rotation(std::f32::consts::FRAC_PI_2),
+    );
+    let child = spawn(&mut world, Transform::from_xy(1.0, 0.0));
+    let grandchild = spawn(&mut world, Transform::from_xy(0.0, 2.0));
+    let other_root = spawn(&mut world, Transform::from_xy(5.0, 5.0));
+    world.entity_mut(root).push_children(&[child]);
+    world.entity_mut(child).push_children(&[grandchild]);
+
+    schedule.run(&mut world);
+    let translation =
+        |world: &World, entity| world.get::<GlobalTransform>(entity).unwrap().translation();
+    assert!(translation(&world, child).abs_diff_eq(Vec2::new(1.0, 1.0), 1e-6));
+    assert!(translation(&world, grandchild).abs_diff_eq(Vec2::new(-1.0, 1.0), 1e-6));
+    assert_eq!(translation(&world, other_root), Vec2::new(5.0, 5.0));
+
+    // nothing changed, so nothing is recomputed and the stale value stays
+    let stale = GlobalTransform::from(Transform::from_xy(9.0, 9.0));
+    *world
+        .get_mut::<GlobalTransform>(grandchild)
+        .unwrap()
+        .bypass_change_detection() = stale;
+    schedule.run(&mut world);
+    assert_eq!(translation(&world, grandchild), Vec2::new(9.0, 9.0));
+
+    // changing a parent updates everything below it
+    world.get_mut::<Transform>(child).unwrap().x = 2.0;
+    schedule.run(&mut world);
+    assert!(translation(&world, child).abs_diff_eq(Vec2::new(1.0, 2.0), 1e-6));
+    assert!(translation(&world, grandchild).abs_diff_eq(Vec2::new(-1.0, 2.0), 1e-6));
+
+    // an entity that loses its parent becomes a root
+    world.entity_mut(grandchild).remove_parent();
+    schedule.run(&mut world);
+    assert_eq!(translation(&world, grandchild), Vec2::new(0.0, 2.0));
+}
+
+#[cfg(test)]
+fn transform_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((TimePlugin, TransformPlugin));
+    app
+}
+
+#[cfg(test)]
+fn global_translation(app: &App, entity: Entity) -> Option<Vec2> {
+    app.world
+        .get::<GlobalTransform>(entity)
+        .map(GlobalTransform::translation)
+}
+
+#[test]
+fn hierarchy_spawn() {
+    let mut app = transform_app();
+    let mut child = None;
+    let parent = app
+        .world
+        .spawn(Transform::from_xy(1.0, 2.0).with_scale(2.0))
+        .with_children(|parent| {
+            child = Some(parent.spawn(Transform::from_xy(1.0, 0.0)).id());
+        })
+        .id();
+    let child = child.unwrap();
+
+    // correct on the first frame, before anything has changed
+    app.update();
+    assert_eq!(global_translation(&app, parent), Some(Vec2::new(1.0, 2.0)));
+    assert_eq!(global_translation(&app, child), Some(Vec2::new(3.0, 2.0)));
+
+    // also for a child spawned under a parent that already has a global transform
+    let grandchild = app.world.spawn(Transform::from_xy(0.0, 1.0)).id();
+    app.world.entity_mut(child).push_children(&[grandchild]);
+    app.update();
+    assert_eq!(
+        global_translation(&app, grandchild),
+        Some(Vec2::new(3.0, 4.0))
+    );
+
+    app.world.get_mut::<Transform>(parent).unwrap().x = 0.0;
+    app.update();
+    assert_eq!(global_translation(&app, child), Some(Vec2::new(2.0, 2.0)));
+    assert_eq!(
+        global_translation(&app, grandchild),
+        Some(Vec2::new(2.0, 4.0))
+    );
+}
+
+#[test]
+fn hierarchy_reparent() {
+    let mut app = transform_app();
+    let a = app.world.spawn(Transform::from_xy(10.0, 0.0)).id();
+    let b = app
+        .world
+        .spawn(Transform::from_xy(0.0, 10.0).with_rotation(std::f32::consts::PI))
+        .id();
+    let child = app.world.spawn(Transform::from_xy(1.0, 0.0)).id();
+    app.world.entity_mut(a).push_children(&[child]);
+    app.update();
+    assert_eq!(global_translation(&app, child), Some(Vec2::new(11.0, 0.0)));
+
+    app.world.entity_mut(child).set_parent(b);
+    app.update();
+    let translation = global_translation(&app, child).unwrap();
+    assert!(translation.abs_diff_eq(Vec2::new(-1.0, 10.0), 1e-6));
+
+    app.world.entity_mut(child).remove_parent();
+    app.update();
+    assert_eq!(global_translation(&app, child), Some(Vec2::new(1.0, 0.0)));
+}
+
+#[test]
+fn hierarchy_remove_transform() {
+    let mut app = transform_app();
+    let entity = app.world.spawn(Transform::from_xy(1.0, 1.0)).id();
+    app.update();
+    assert_eq!(global_translation(&app, entity), Some(Vec2::new(1.0, 1.0)));
+
+    app.world.entity_mut(entity).remove::<Transform>();
+    app.update();
+    assert_eq!(global_translation(&app, entity), None);
+
+    app.world
+        .entity_mut(entity)
+        .insert(Transform::from_xy(2.0, 3.0));
+    app.update();
+    assert_eq!(global_translation(&app, entity), Some(Vec2::new(2.0, 3.0)));
+}
+
+#[test]
+fn hierarchy_despawn_parent() {
+    let mut app = transform_app();
+    let parent = app.world.spawn(Transform::from_xy(5.0, 0.0)).id();
+    let kept = app.world.spawn(Transform::from_xy(1.0, 0.0)).id();
+    let despawned = app.world.spawn(Transform::from_xy(2.0, 0.0)).id();
+    app.world
+        .entity_mut(parent)
+        .push_children(&[kept, despawned]);
+    app.update();
+    assert_eq!(global_translation(&app, kept), Some(Vec2::new(6.0, 0.0)));
+
+    // children that are detached first become roots
+    app.world.entity_mut(kept).remove_parent();
+    app.world.entity_mut(parent).despawn_recursive();
+    app.update();
+    assert!(app.world.get_entity(despawned).is_none());
+    assert_eq!(global_translation(&app, kept), Some(Vec2::new(1.0, 0.0)));
+}
+
+#[cfg(test)]
+fn hierarchy_errors(app: &App) -> Vec<HierarchyError> {
+    let events = app.world.resource::<Events<HierarchyError>>();
+    events.get_reader().iter(events).cloned().collect()
+}
+
+#[test]
+fn hierarchy_dangling_parent() {
+    let mut app = transform_app();
+    let parent = app.world.spawn(Transform::from_xy(5.0, 0.0)).id();
+    let child = app.world.spawn(Transform::from_xy(1.0, 0.0)).id();
+    let grandchild = app.world.spawn(Transform::from_xy(0.0, 1.0)).id();
+    app.world.entity_mut(parent).push_children(&[child]);
+    app.world.entity_mut(child).push_children(&[grandchild]);
+    app.update();
+    assert_eq!(
+        global_translation(&app, grandchild),
+        Some(Vec2::new(6.0, 1.0))
+    );
+    assert!(hierarchy_errors(&app).is_empty());
+
+    // despawning only the parent leaves the child pointing at nothing
+    app.world.despawn(parent);
+    app.update();
+    assert_eq!(
+        hierarchy_errors(&app),
+        [HierarchyError::DanglingParent {
+            entity: child,
+            parent
+        }]
+    );
+    assert_eq!(global_translation(&app, child), Some(Vec2::new(1.0, 0.0)));
+    assert_eq!(
+        global_translation(&app, grandchild),
+        Some(Vec2::new(1.0, 1.0))
+    );
+
+    // reported once, but still propagated as a root
+    app.world.get_mut::<Transform>(child).unwrap().y = 2.0;
+    app.update();
+    app.update();
+    assert!(hierarchy_errors(&app).is_empty());
+    assert_eq!(
+        global_translation(&app, grandchild),
+        Some(Vec2::new(1.0, 3.0))
+    );
+}
+
+#[test]
+fn hierarchy_dangling_siblings() {
+    let mut app = transform_app();
+    // reusing despawned entities hands them out in reverse, so the children aren't stored in order
+    let reused: Vec<Entity> = (0..4).map(|_| app.world.spawn_empty().id()).collect();
+    for entity in reused {
+        app.world.despawn(entity);
+    }
+    let parent = app.world.spawn(Transform::IDENTITY).id();
+    let mut children: Vec<Entity> = (0..4)
+        .map(|i| app.world.spawn(Transform::from_xy(i as f32, 0.0)).id())
+        .collect();
+    app.world.entity_mut(parent).push_children(&children);
+    app.update();
+
+    app.world.despawn(parent);
+    app.update();
+    app.update();
+    children.sort_unstable();
+    let expected: Vec<_> = children
+        .iter()
+        .map(|&entity| HierarchyError::DanglingParent { entity, parent })
+        .collect();
+    assert_eq!(hierarchy_errors(&app), expected);
+
+    app.update();
+    app.update();
+    assert!(hierarchy_errors(&app).is_empty());
+}
+
+#[test]
+fn hierarchy_cycle() {
+    let mut app = transform_app();
+    let root = app.world.spawn(Transform::from_xy(5.0, 0.0)).id();
+    let a = app.world.spawn(Transform::from_xy(1.0, 0.0)).id();
+    let b = app.world.spawn(Transform::from_xy(0.0, 1.0)).id();
+    let c = app.world.spawn(Transform::from_xy(1.0, 1.0)).id();
+    app.world.entity_mut(root).push_children(&[a]);
+    app.world.entity_mut(a).push_children(&[b]);
+    app.world.entity_mut(b).push_children(&[c]);
+    app.update();
+    assert_eq!(global_translation(&app, c), Some(Vec2::new(7.0, 2.0)));
+
+    app.world.entity_mut(c).push_children(&[a]);
+    app.update();
+    assert_eq!(
+        hierarchy_errors(&app),
+        [HierarchyError::Cycle(vec![a, c, b])]
+    );
+    assert_eq!(global_translation(&app, c), Some(Vec2::new(7.0, 2.0)));
+
+    // new entities under the cycle still get a transform
+    let d = app.world.spawn(Transform::from_xy(3.0, 3.0)).id();
+    app.world.entity_mut(b).push_children(&[d]);
+    app.update();
+    assert_eq!(global_translation(&app, d), Some(Vec2::new(3.0, 3.0)));
+
+    // touching a parent inside the cycle doesn't report it again
+    app.world.get_mut::<Parent>(c).unwrap().set_changed();
+    app.update();
+    app.update();
+    assert!(hierarchy_errors(&app).is_empty());
+
+    // breaking the cycle makes `a` a root again
+    app.world.entity_mut(a).remove_parent();
+    app.update();
+    assert_eq!(global_translation(&app, c), Some(Vec2::new(2.0, 2.0)));
+}
+
+#[test]
+fn rotation_interpolation() {
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    let transform = Transform::from_xy(2.0, 0.0).with_scale_xy(2.0, 3.0);
+    let previous = PreviousGlobalTransform(transform.to_affine());
+    let blend = |rotation, alpha| {
+        previous
+            .interpolate(&transform.with_rotation(rotation).into(), alpha)
+            .0
+    };
+
+    // halfway through a quarter turn is an eighth of a turn, at the same scale
+    let halfway = blend(FRAC_PI_2, 0.5);
+    let expected = transform.with_rotation(FRAC_PI_4).to_affine();
+    assert!(halfway.abs_diff_eq(expected, 1e-6));
+    assert!((halfway.x_axis.length() - 2.0).abs() < 1e-6);
+    assert!((halfway.y_axis.length() - 3.0).abs() < 1e-6);
+
+    // turning the shorter way round, and keeping the size through a half turn
+    let expected = transform.with_rotation(-FRAC_PI_4).to_affine();
+    assert!(blend(-FRAC_PI_2, 0.5).abs_diff_eq(expected, 1e-6));
+    let expected = transform.with_rotation(-FRAC_PI_4).to_affine();
+    assert!(blend(3.0 * PI / 2.0, 0.5).abs_diff_eq(expected, 1e-6));
+    let halfway = blend(PI, 0.5);
+    assert!((halfway.x_axis.length() - 2.0).abs() < 1e-6);
+    assert!((halfway.y_axis.length() - 3.0).abs() < 1e-6);
+}
+
+#[test]
+fn transform_interpolation() {
+    let mut app = transform_app();
+    let entity = app.world.spawn(Transform::IDENTITY).id();
+    app.add_systems(
+        FixedUpdate,
+        |mut transforms: Query<'_, '_, &mut Transform>| {
+            for mut transform in &mut transforms {
+                transform.x += 1.0;
+            }
+        },
+    );
+    let fixed_timestep = std::time::Duration::from_secs(3600);
+    app.world
+        .resource_mut::<time::Time>()
+        .set_fixed_timestep(fixed_timestep);
+    app.update();
+
+    // bank exactly one step, as real time is far too slow for it
+    let mut time = app.world.resource_mut::<time::Time>();
+    time.set_max_delta(fixed_timestep);
+    time.update_with_instant(std::time::Instant::now() + fixed_timestep);
+    app.update();
+    let previous = *app.world.get::<PreviousGlobalTransform>(entity).unwrap();
+    let current = *app.world.get::<GlobalTransform>(entity).unwrap();
+    assert_eq!(previous.0.translation, Vec2::ZERO);
+    assert_eq!(current.translation(), Vec2::new(1.0, 0.0));
+    assert_eq!(
+        previous.interpolate(&current, 0.25).translation(),
+        Vec2::new(0.25, 0.0)
+    );
+
+    // moving outside of a fixed step doesn't blend
+    app.world.get_mut::<Transform>(entity).unwrap().y = 1.0;
+    app.update();
+    let previous = *app.world.get::<PreviousGlobalTransform>(entity).unwrap();
+    assert_eq!(previous.0.translation, Vec2::new(1.0, 1.0));
+}
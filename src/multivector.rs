@@ -1,26 +1,168 @@
-use std::ops::{Add, Mul, Neg, Sub};
-
-pub type T = f32;
+use crate::{
+    fixed::Fixed,
+    simd::{F32Lanes, F32x8},
+};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{
+        Add, AddAssign, BitAnd, BitOr, BitXor, Div, DivAssign, Mul, MulAssign, Neg, Shl, Shr, Sub,
+        SubAssign,
+    },
+    str::FromStr,
+};
 
 mod sealed {
-    use super::*;
-
     pub trait Sealed {}
-    impl Sealed for Zero {}
-    impl Sealed for T {}
 }
 
-pub trait Value: sealed::Sealed + Copy {}
-impl Value for Zero {}
-impl Value for T {}
+/// A type that can be stored in a component of a [`GenericMultiVector`], either [`Zero`] or a [`Number`]
+pub trait Value: sealed::Sealed + Copy {
+    /// Whether this is [`Zero`], components of this type are left out when displaying or serializing
+    const IS_ZERO: bool;
+}
 
-#[derive(Debug, Clone, Copy)]
-pub struct Zero;
+impl Value for Zero {
+    const IS_ZERO: bool = true;
+}
+
+impl<T: Number> Value for T {
+    const IS_ZERO: bool = false;
+}
+
+impl sealed::Sealed for Zero {}
+impl<T: Number> sealed::Sealed for T {}
+
+/// A scalar type that the multivectors can be built from
+pub trait Number:
+    Copy
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Add<Zero, Output = Self>
+    + Sub<Zero, Output = Self>
+    + Mul<Zero, Output = Zero>
+    + From<Zero>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const HALF: Self;
+}
+
+/// A [`Number`] that also supports the functions needed for norms, rotations and logarithms
+pub trait Real: Number + PartialOrd {
+    const EPSILON: Self;
+
+    fn abs(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn atan2(self, other: Self) -> Self;
+    /// The number of representable values between `self` and `other`
+    fn ulps(self, other: Self) -> u64;
+
+    /// The sign of the cross product of `a - origin` and `b - origin`, with points as `(x, y)`
+    ///
+    /// Types whose arithmetic rounds or saturates can compute this exactly instead
+    #[inline]
+    fn cross_sign(origin: (Self, Self), a: (Self, Self), b: (Self, Self)) -> Ordering {
+        let cross = (a.0 - origin.0) * (b.1 - origin.1) - (a.1 - origin.1) * (b.0 - origin.0);
+        cross.partial_cmp(&Self::ZERO).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// A [`Value`] that can be read as a `T`, this is how methods like [`GenericMultiVector::bulk_norm`]
+/// find out which scalar type they are working with
+pub trait Component<T: Number>: Value {
+    fn get(self) -> T;
+}
+
+impl<T: Number> Component<T> for Zero {
+    #[inline]
+    fn get(self) -> T {
+        T::ZERO
+    }
+}
 
-impl From<Zero> for T {
+impl<T: Number> Component<T> for T {
     #[inline]
-    fn from(Zero: Zero) -> Self {
-        0.0
+    fn get(self) -> T {
+        self
+    }
+}
+
+/// A multivector whose components can all be read as a `T`, used to compare multivectors whose
+/// [`Zero`] components are in different places
+pub trait Components<T: Number>: Copy {
+    /// The components in field order
+    fn components(self) -> [T; 8];
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Zero;
+
+/// A missing field deserializes as `Zero`, so that zero components can be left out while missing
+/// number components are still an error
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Zero {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ZeroVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ZeroVisitor {
+            type Value = Zero;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("nothing, as the component is always zero")
+            }
+
+            fn visit_none<E: serde::de::Error>(self) -> Result<Zero, E> {
+                Ok(Zero)
+            }
+
+            fn visit_unit<E: serde::de::Error>(self) -> Result<Zero, E> {
+                Ok(Zero)
+            }
+
+            fn visit_some<D: serde::Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Zero, D::Error> {
+                <() as serde::Deserialize<'de>>::deserialize(deserializer).map(|()| Zero)
+            }
+        }
+
+        deserializer.deserialize_option(ZeroVisitor)
+    }
+}
+
+impl fmt::Display for Zero {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseZeroError;
+
+impl fmt::Display for ParseZeroError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a number equal to zero")
+    }
+}
+
+impl std::error::Error for ParseZeroError {}
+
+impl FromStr for Zero {
+    type Err = ParseZeroError;
+
+    /// Accepts any number that is zero, like `0` or `-0.0`
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.parse::<f64>() {
+            Ok(0.0) => Ok(Zero),
+            _ => Err(ParseZeroError),
+        }
     }
 }
 
@@ -42,7 +184,25 @@ impl Add<Zero> for Zero {
     }
 }
 
-impl Add<T> for Zero {
+impl Sub<Zero> for Zero {
+    type Output = Zero;
+
+    #[inline]
+    fn sub(self, Zero: Zero) -> Self::Output {
+        self
+    }
+}
+
+impl Mul<Zero> for Zero {
+    type Output = Zero;
+
+    #[inline]
+    fn mul(self, Zero: Zero) -> Self::Output {
+        self
+    }
+}
+
+impl<T: Number> Add<T> for Zero {
     type Output = T;
 
     #[inline]
@@ -51,43 +211,391 @@ impl Add<T> for Zero {
     }
 }
 
-impl Add<Zero> for T {
+impl<T: Number> Sub<T> for Zero {
     type Output = T;
 
     #[inline]
-    fn add(self, Zero: Zero) -> Self::Output {
+    fn sub(self, rhs: T) -> Self::Output {
+        -rhs
+    }
+}
+
+impl<T: Number> Mul<T> for Zero {
+    type Output = Zero;
+
+    #[inline]
+    fn mul(self, _: T) -> Self::Output {
         self
     }
 }
 
-impl Sub<Zero> for Zero {
+impl<T: Number> Div<T> for Zero {
     type Output = Zero;
 
     #[inline]
-    fn sub(self, Zero: Zero) -> Self::Output {
+    fn div(self, _: T) -> Self::Output {
         self
     }
 }
 
-impl Sub<T> for Zero {
-    type Output = T;
+// the orphan rules don't allow implementing these for every `T: Number` at once
+macro_rules! impl_zero_ops {
+    ([$($generics:tt)*] $t:ty) => {
+        impl<$($generics)*> From<Zero> for $t {
+            #[inline]
+            fn from(Zero: Zero) -> Self {
+                <$t as Number>::ZERO
+            }
+        }
+
+        impl<$($generics)*> Add<Zero> for $t {
+            type Output = $t;
+
+            #[inline]
+            fn add(self, Zero: Zero) -> Self::Output {
+                self
+            }
+        }
+
+        impl<$($generics)*> Sub<Zero> for $t {
+            type Output = $t;
+
+            #[inline]
+            fn sub(self, Zero: Zero) -> Self::Output {
+                self
+            }
+        }
+
+        impl<$($generics)*> Mul<Zero> for $t {
+            type Output = Zero;
+
+            #[inline]
+            fn mul(self, Zero: Zero) -> Self::Output {
+                Zero
+            }
+        }
+    };
+    ($t:ty) => {
+        impl_zero_ops!([] $t);
+    };
+}
+
+// scaling by a plain number, which has to be implemented for every scalar type for the same reason
+macro_rules! impl_scalar_ops {
+    ([$($generics:tt)*] $t:ty) => {
+        impl<$($generics)* S, E0, E1, E2, E01, E02, E12, E012> Mul<$t> for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+        where
+            S: Value + Mul<$t>,
+            E0: Value + Mul<$t>,
+            E1: Value + Mul<$t>,
+            E2: Value + Mul<$t>,
+            E01: Value + Mul<$t>,
+            E02: Value + Mul<$t>,
+            E12: Value + Mul<$t>,
+            E012: Value + Mul<$t>,
+            MO<S, $t>: Value,
+            MO<E0, $t>: Value,
+            MO<E1, $t>: Value,
+            MO<E2, $t>: Value,
+            MO<E01, $t>: Value,
+            MO<E02, $t>: Value,
+            MO<E12, $t>: Value,
+            MO<E012, $t>: Value,
+        {
+            type Output = GenericMultiVector<
+                MO<S, $t>,
+                MO<E0, $t>,
+                MO<E1, $t>,
+                MO<E2, $t>,
+                MO<E01, $t>,
+                MO<E02, $t>,
+                MO<E12, $t>,
+                MO<E012, $t>,
+            >;
+
+            #[inline]
+            fn mul(self, rhs: $t) -> Self::Output {
+                Self::Output {
+                    s: self.s * rhs,
+                    e0: self.e0 * rhs,
+                    e1: self.e1 * rhs,
+                    e2: self.e2 * rhs,
+                    e01: self.e01 * rhs,
+                    e02: self.e02 * rhs,
+                    e12: self.e12 * rhs,
+                    e012: self.e012 * rhs,
+                }
+            }
+        }
+
+        impl<$($generics)* S, E0, E1, E2, E01, E02, E12, E012> Div<$t> for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+        where
+            S: Value + Div<$t>,
+            E0: Value + Div<$t>,
+            E1: Value + Div<$t>,
+            E2: Value + Div<$t>,
+            E01: Value + Div<$t>,
+            E02: Value + Div<$t>,
+            E12: Value + Div<$t>,
+            E012: Value + Div<$t>,
+            DO<S, $t>: Value,
+            DO<E0, $t>: Value,
+            DO<E1, $t>: Value,
+            DO<E2, $t>: Value,
+            DO<E01, $t>: Value,
+            DO<E02, $t>: Value,
+            DO<E12, $t>: Value,
+            DO<E012, $t>: Value,
+        {
+            type Output = GenericMultiVector<
+                DO<S, $t>,
+                DO<E0, $t>,
+                DO<E1, $t>,
+                DO<E2, $t>,
+                DO<E01, $t>,
+                DO<E02, $t>,
+                DO<E12, $t>,
+                DO<E012, $t>,
+            >;
+
+            #[inline]
+            fn div(self, rhs: $t) -> Self::Output {
+                Self::Output {
+                    s: self.s / rhs,
+                    e0: self.e0 / rhs,
+                    e1: self.e1 / rhs,
+                    e2: self.e2 / rhs,
+                    e01: self.e01 / rhs,
+                    e02: self.e02 / rhs,
+                    e12: self.e12 / rhs,
+                    e012: self.e012 / rhs,
+                }
+            }
+        }
+
+        impl<$($generics)* S, E0, E1, E2, E01, E02, E12, E012> Mul<GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>> for $t
+        where
+            $t: Mul<S>,
+            $t: Mul<E0>,
+            $t: Mul<E1>,
+            $t: Mul<E2>,
+            $t: Mul<E01>,
+            $t: Mul<E02>,
+            $t: Mul<E12>,
+            $t: Mul<E012>,
+            S: Value,
+            E0: Value,
+            E1: Value,
+            E2: Value,
+            E01: Value,
+            E02: Value,
+            E12: Value,
+            E012: Value,
+            MO<$t, S>: Value,
+            MO<$t, E0>: Value,
+            MO<$t, E1>: Value,
+            MO<$t, E2>: Value,
+            MO<$t, E01>: Value,
+            MO<$t, E02>: Value,
+            MO<$t, E12>: Value,
+            MO<$t, E012>: Value,
+        {
+            type Output = GenericMultiVector<
+                MO<$t, S>,
+                MO<$t, E0>,
+                MO<$t, E1>,
+                MO<$t, E2>,
+                MO<$t, E01>,
+                MO<$t, E02>,
+                MO<$t, E12>,
+                MO<$t, E012>,
+            >;
+
+            #[inline]
+            fn mul(self, rhs: GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>) -> Self::Output {
+                GenericMultiVector {
+                    s: self * rhs.s,
+                    e0: self * rhs.e0,
+                    e1: self * rhs.e1,
+                    e2: self * rhs.e2,
+                    e01: self * rhs.e01,
+                    e02: self * rhs.e02,
+                    e12: self * rhs.e12,
+                    e012: self * rhs.e012,
+                }
+            }
+        }
+    };
+    ($t:ty) => {
+        impl_scalar_ops!([] $t);
+    };
+}
+
+macro_rules! impl_float {
+    ($t:ident, $bits:ident) => {
+        impl Number for $t {
+            const ZERO: Self = 0.0;
+            const ONE: Self = 1.0;
+            const HALF: Self = 0.5;
+        }
+
+        impl Real for $t {
+            const EPSILON: Self = $t::EPSILON;
+
+            #[inline]
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+
+            #[inline]
+            fn sqrt(self) -> Self {
+                $t::sqrt(self)
+            }
+
+            #[inline]
+            fn sin_cos(self) -> (Self, Self) {
+                $t::sin_cos(self)
+            }
+
+            #[inline]
+            fn atan2(self, other: Self) -> Self {
+                $t::atan2(self, other)
+            }
+
+            #[inline]
+            fn ulps(self, other: Self) -> u64 {
+                // maps the bits so that they are ordered like the floats, with both zeros at 0
+                let ordered = |value: $t| {
+                    let bits = value.to_bits() as $bits;
+                    if bits < 0 {
+                        $bits::MIN - bits
+                    } else {
+                        bits
+                    }
+                };
+                (ordered(self) as i128 - ordered(other) as i128).unsigned_abs() as u64
+            }
+        }
+
+        impl_zero_ops!($t);
+        impl_scalar_ops!($t);
+    };
+}
+
+impl_float!(f32, i32);
+impl_float!(f64, i64);
+
+impl Number for Fixed {
+    const ZERO: Self = Fixed::ZERO;
+    const ONE: Self = Fixed::ONE;
+    const HALF: Self = Fixed::HALF;
+}
+
+impl Real for Fixed {
+    const EPSILON: Self = Fixed::EPSILON;
 
     #[inline]
-    fn sub(self, rhs: T) -> Self::Output {
-        -rhs
+    fn abs(self) -> Self {
+        Fixed::abs(self)
+    }
+
+    #[inline]
+    fn sqrt(self) -> Self {
+        Fixed::sqrt(self)
+    }
+
+    #[inline]
+    fn sin_cos(self) -> (Self, Self) {
+        Fixed::sin_cos(self)
+    }
+
+    #[inline]
+    fn atan2(self, other: Self) -> Self {
+        Fixed::atan2(self, other)
+    }
+
+    #[inline]
+    fn ulps(self, other: Self) -> u64 {
+        self.to_bits().abs_diff(other.to_bits())
+    }
+
+    /// Exact, the differences of the bits take 65 bits and their products 129, so the products
+    /// are compared by their signs and then by their magnitudes, which fit in a `u128`
+    fn cross_sign(origin: (Self, Self), a: (Self, Self), b: (Self, Self)) -> Ordering {
+        let difference = |a: Fixed, b: Fixed| a.to_bits() as i128 - b.to_bits() as i128;
+        let product =
+            |x: i128, y: i128| (x.signum() * y.signum(), x.unsigned_abs() * y.unsigned_abs());
+        let (left_sign, left) = product(difference(a.0, origin.0), difference(b.1, origin.1));
+        let (right_sign, right) = product(difference(a.1, origin.1), difference(b.0, origin.0));
+        match (left_sign, right_sign) {
+            (1, 1) => left.cmp(&right),
+            (-1, -1) => right.cmp(&left),
+            _ => left_sign.cmp(&right_sign),
+        }
     }
 }
 
-impl Sub<Zero> for T {
-    type Output = T;
+impl_zero_ops!(Fixed);
+impl_scalar_ops!(Fixed);
+
+impl<const N: usize> Number for F32Lanes<N> {
+    const ZERO: Self = F32Lanes::splat(0.0);
+    const ONE: Self = F32Lanes::splat(1.0);
+    const HALF: Self = F32Lanes::splat(0.5);
+}
+
+impl_zero_ops!([const N: usize] F32Lanes<N>);
+impl_scalar_ops!([const N: usize,] F32Lanes<N>);
+
+/// A [`Value`] made of `N` lanes, a multivector built from these evaluates `N` multivectors at once
+pub trait Batch<const N: usize>: Value {
+    type Lane: Value;
+
+    fn splat(lane: Self::Lane) -> Self;
+    fn from_lanes(lanes: [Self::Lane; N]) -> Self;
+    fn lane(self, index: usize) -> Self::Lane;
+}
+
+impl<const N: usize> Batch<N> for Zero {
+    type Lane = Zero;
 
     #[inline]
-    fn sub(self, Zero: Zero) -> Self::Output {
-        self
+    fn splat(Zero: Zero) -> Self {
+        Zero
+    }
+
+    #[inline]
+    fn from_lanes(_: [Zero; N]) -> Self {
+        Zero
+    }
+
+    #[inline]
+    fn lane(self, _: usize) -> Self::Lane {
+        Zero
+    }
+}
+
+impl<const N: usize> Batch<N> for F32Lanes<N> {
+    type Lane = f32;
+
+    #[inline]
+    fn splat(lane: f32) -> Self {
+        F32Lanes::splat(lane)
+    }
+
+    #[inline]
+    fn from_lanes(lanes: [f32; N]) -> Self {
+        F32Lanes(lanes)
+    }
+
+    #[inline]
+    fn lane(self, index: usize) -> Self::Lane {
+        self.0[index]
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
 where
     S: Value,
@@ -99,23 +607,32 @@ where
     E12: Value,
     E012: Value,
 {
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub s: S,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub e0: E0,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub e1: E1,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub e2: E2,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub e01: E01,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub e02: E02,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub e12: E12,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
     pub e012: E012,
 }
 
-pub type MultiVector = GenericMultiVector<T, T, T, T, T, T, T, T>;
-pub type Scalar = GenericMultiVector<T, Zero, Zero, Zero, Zero, Zero, Zero, Zero>;
-pub type Vector = GenericMultiVector<Zero, T, T, T, Zero, Zero, Zero, Zero>;
-pub type Line = Vector;
-pub type BiVector = GenericMultiVector<Zero, Zero, Zero, Zero, T, T, T, Zero>;
-pub type Point = BiVector;
-pub type TriVector = GenericMultiVector<Zero, Zero, Zero, Zero, Zero, Zero, Zero, T>;
+pub type MultiVector<T = f32> = GenericMultiVector<T, T, T, T, T, T, T, T>;
+pub type Scalar<T = f32> = GenericMultiVector<T, Zero, Zero, Zero, Zero, Zero, Zero, Zero>;
+pub type Vector<T = f32> = GenericMultiVector<Zero, T, T, T, Zero, Zero, Zero, Zero>;
+pub type Line<T = f32> = Vector<T>;
+pub type BiVector<T = f32> = GenericMultiVector<Zero, Zero, Zero, Zero, T, T, T, Zero>;
+pub type Point<T = f32> = BiVector<T>;
+pub type TriVector<T = f32> = GenericMultiVector<Zero, Zero, Zero, Zero, Zero, Zero, Zero, T>;
+pub type Motor<T = f32> = GenericMultiVector<T, Zero, Zero, Zero, T, T, T, Zero>;
 
 impl<S, E0, E1, E2, E01, E02, E12, E012> GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
 where
@@ -163,6 +680,310 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+#[inline]
+pub(crate) fn is_zero<V: Value>(_: &V) -> bool {
+    V::IS_ZERO
+}
+
+impl<S, E0, E1, E2, E01, E02, E12, E012> fmt::Display
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value + fmt::Display,
+    E0: Value + fmt::Display,
+    E1: Value + fmt::Display,
+    E2: Value + fmt::Display,
+    E01: Value + fmt::Display,
+    E02: Value + fmt::Display,
+    E12: Value + fmt::Display,
+    E012: Value + fmt::Display,
+{
+    /// Writes the non-zero components in blade notation, like `2 + 3e0 - 1.5e12`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn text<V: Value + fmt::Display>(value: &V, f: &fmt::Formatter<'_>) -> Option<String> {
+            if V::IS_ZERO {
+                return None;
+            }
+            let text = match f.precision() {
+                Some(precision) => format!("{value:.precision$}"),
+                None => value.to_string(),
+            };
+            let is_zero = text
+                .trim_start_matches('-')
+                .chars()
+                .all(|c| c == '0' || c == '.');
+            (!is_zero).then_some(text)
+        }
+
+        let terms = [
+            (text(&self.s, f), ""),
+            (text(&self.e0, f), "e0"),
+            (text(&self.e1, f), "e1"),
+            (text(&self.e2, f), "e2"),
+            (text(&self.e01, f), "e01"),
+            (text(&self.e02, f), "e02"),
+            (text(&self.e12, f), "e12"),
+            (text(&self.e012, f), "e012"),
+        ];
+        let mut first = true;
+        for (text, blade) in terms {
+            let Some(text) = text else {
+                continue;
+            };
+            match (first, text.strip_prefix('-')) {
+                (true, _) => write!(f, "{text}{blade}")?,
+                (false, Some(magnitude)) => write!(f, " - {magnitude}{blade}")?,
+                (false, None) => write!(f, " + {text}{blade}")?,
+            }
+            first = false;
+        }
+        if first {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMultiVectorError {
+    Empty,
+    /// A `+` or `-` without a term after it
+    MissingTerm,
+    UnknownBlade(String),
+    /// The coefficient couldn't be parsed, or its blade is always [`Zero`] in this type
+    InvalidCoefficient {
+        blade: &'static str,
+        coefficient: String,
+    },
+}
+
+impl fmt::Display for ParseMultiVectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMultiVectorError::Empty => write!(f, "no terms were given"),
+            ParseMultiVectorError::MissingTerm => write!(f, "expected a term after the sign"),
+            ParseMultiVectorError::UnknownBlade(blade) => write!(f, "unknown blade `{blade}`"),
+            ParseMultiVectorError::InvalidCoefficient {
+                blade: "",
+                coefficient,
+            } => {
+                write!(f, "invalid scalar `{coefficient}`")
+            }
+            ParseMultiVectorError::InvalidCoefficient { blade, coefficient } => {
+                write!(f, "invalid coefficient `{coefficient}` for `{blade}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMultiVectorError {}
+
+impl<S, E0, E1, E2, E01, E02, E12, E012> FromStr
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value + FromStr + From<Zero> + Add<Output = S>,
+    E0: Value + FromStr + From<Zero> + Add<Output = E0>,
+    E1: Value + FromStr + From<Zero> + Add<Output = E1>,
+    E2: Value + FromStr + From<Zero> + Add<Output = E2>,
+    E01: Value + FromStr + From<Zero> + Add<Output = E01>,
+    E02: Value + FromStr + From<Zero> + Add<Output = E02>,
+    E12: Value + FromStr + From<Zero> + Add<Output = E12>,
+    E012: Value + FromStr + From<Zero> + Add<Output = E012>,
+{
+    type Err = ParseMultiVectorError;
+
+    /// Parses blade notation like `2 + 3e0 - 1.5e12`
+    ///
+    /// A blade without a coefficient like `e12` means `1e12`, and coefficients can't use
+    /// exponent notation since `e` starts the blade
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        fn parse<V: FromStr>(
+            blade: &'static str,
+            coefficient: String,
+        ) -> Result<V, ParseMultiVectorError> {
+            coefficient
+                .parse()
+                .map_err(|_| ParseMultiVectorError::InvalidCoefficient { blade, coefficient })
+        }
+
+        let text = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        if text.is_empty() {
+            return Err(ParseMultiVectorError::Empty);
+        }
+
+        let mut result = Self {
+            s: Zero.into(),
+            e0: Zero.into(),
+            e1: Zero.into(),
+            e2: Zero.into(),
+            e01: Zero.into(),
+            e02: Zero.into(),
+            e12: Zero.into(),
+            e012: Zero.into(),
+        };
+        let mut rest = text.as_str();
+        while !rest.is_empty() {
+            let end = rest
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '+' || c == '-')
+                .map_or(rest.len(), |(i, _)| i);
+            let (term, remaining) = rest.split_at(end);
+            rest = remaining;
+
+            let (sign, term) = if let Some(term) = term.strip_prefix('-') {
+                ("-", term)
+            } else {
+                ("", term.strip_prefix('+').unwrap_or(term))
+            };
+            if term.is_empty() {
+                return Err(ParseMultiVectorError::MissingTerm);
+            }
+            let (coefficient, blade) = term.split_at(term.find('e').unwrap_or(term.len()));
+            let coefficient = match coefficient {
+                "" => format!("{sign}1"),
+                coefficient => format!("{sign}{coefficient}"),
+            };
+            match blade {
+                "" => result.s = result.s + parse("", coefficient)?,
+                "e0" => result.e0 = result.e0 + parse("e0", coefficient)?,
+                "e1" => result.e1 = result.e1 + parse("e1", coefficient)?,
+                "e2" => result.e2 = result.e2 + parse("e2", coefficient)?,
+                "e01" => result.e01 = result.e01 + parse("e01", coefficient)?,
+                "e02" => result.e02 = result.e02 + parse("e02", coefficient)?,
+                "e12" => result.e12 = result.e12 + parse("e12", coefficient)?,
+                "e012" => result.e012 = result.e012 + parse("e012", coefficient)?,
+                blade => return Err(ParseMultiVectorError::UnknownBlade(blade.to_string())),
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl<S, E0, E1, E2, E01, E02, E12, E012> GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E01: Value,
+    E02: Value,
+    E12: Value,
+    E012: Value,
+{
+    /// Puts `value` in every lane of a batch
+    #[inline]
+    pub fn splat<const N: usize>(
+        value: GenericMultiVector<
+            S::Lane,
+            E0::Lane,
+            E1::Lane,
+            E2::Lane,
+            E01::Lane,
+            E02::Lane,
+            E12::Lane,
+            E012::Lane,
+        >,
+    ) -> Self
+    where
+        S: Batch<N>,
+        E0: Batch<N>,
+        E1: Batch<N>,
+        E2: Batch<N>,
+        E01: Batch<N>,
+        E02: Batch<N>,
+        E12: Batch<N>,
+        E012: Batch<N>,
+    {
+        Self {
+            s: S::splat(value.s),
+            e0: E0::splat(value.e0),
+            e1: E1::splat(value.e1),
+            e2: E2::splat(value.e2),
+            e01: E01::splat(value.e01),
+            e02: E02::splat(value.e02),
+            e12: E12::splat(value.e12),
+            e012: E012::splat(value.e012),
+        }
+    }
+
+    /// Packs `N` multivectors into one batch, converting them to structure-of-arrays form
+    #[inline]
+    pub fn from_lanes<const N: usize>(
+        lanes: [GenericMultiVector<
+            S::Lane,
+            E0::Lane,
+            E1::Lane,
+            E2::Lane,
+            E01::Lane,
+            E02::Lane,
+            E12::Lane,
+            E012::Lane,
+        >; N],
+    ) -> Self
+    where
+        S: Batch<N>,
+        E0: Batch<N>,
+        E1: Batch<N>,
+        E2: Batch<N>,
+        E01: Batch<N>,
+        E02: Batch<N>,
+        E12: Batch<N>,
+        E012: Batch<N>,
+    {
+        Self {
+            s: S::from_lanes(lanes.map(|lane| lane.s)),
+            e0: E0::from_lanes(lanes.map(|lane| lane.e0)),
+            e1: E1::from_lanes(lanes.map(|lane| lane.e1)),
+            e2: E2::from_lanes(lanes.map(|lane| lane.e2)),
+            e01: E01::from_lanes(lanes.map(|lane| lane.e01)),
+            e02: E02::from_lanes(lanes.map(|lane| lane.e02)),
+            e12: E12::from_lanes(lanes.map(|lane| lane.e12)),
+            e012: E012::from_lanes(lanes.map(|lane| lane.e012)),
+        }
+    }
+
+    /// Unpacks the multivector in lane `index` of a batch
+    #[inline]
+    pub fn lane<const N: usize>(
+        self,
+        index: usize,
+    ) -> GenericMultiVector<
+        S::Lane,
+        E0::Lane,
+        E1::Lane,
+        E2::Lane,
+        E01::Lane,
+        E02::Lane,
+        E12::Lane,
+        E012::Lane,
+    >
+    where
+        S: Batch<N>,
+        E0: Batch<N>,
+        E1: Batch<N>,
+        E2: Batch<N>,
+        E01: Batch<N>,
+        E02: Batch<N>,
+        E12: Batch<N>,
+        E012: Batch<N>,
+    {
+        GenericMultiVector {
+            s: self.s.lane(index),
+            e0: self.e0.lane(index),
+            e1: self.e1.lane(index),
+            e2: self.e2.lane(index),
+            e01: self.e01.lane(index),
+            e02: self.e02.lane(index),
+            e12: self.e12.lane(index),
+            e012: self.e012.lane(index),
+        }
+    }
+}
+
 type NO<A> = <A as Neg>::Output;
 
 impl<S, E0, E1, E2, E01, E02, E12, E012> Neg
@@ -202,33 +1023,387 @@ where
     }
 }
 
-type AO<A, B> = <A as Add<B>>::Output;
-
-impl<LS, LE0, LE1, LE2, LE01, LE02, LE12, LE012, RS, RE0, RE1, RE2, RE01, RE02, RE12, RE012>
-    Add<GenericMultiVector<RS, RE0, RE1, RE2, RE01, RE02, RE12, RE012>>
-    for GenericMultiVector<LS, LE0, LE1, LE2, LE01, LE02, LE12, LE012>
+impl<S, E0, E1, E2, E01, E02, E12, E012> GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
 where
-    LS: Value + Add<RS>,
-    LE0: Value + Add<RE0>,
-    LE1: Value + Add<RE1>,
-    LE2: Value + Add<RE2>,
-    LE01: Value + Add<RE01>,
-    LE02: Value + Add<RE02>,
-    LE12: Value + Add<RE12>,
-    LE012: Value + Add<RE012>,
-    RS: Value,
-    RE0: Value,
-    RE1: Value,
-    RE2: Value,
-    RE01: Value,
-    RE02: Value,
-    RE12: Value,
-    RE012: Value,
-    AO<LS, RS>: Value,
-    AO<LE0, RE0>: Value,
-    AO<LE1, RE1>: Value,
-    AO<LE2, RE2>: Value,
-    AO<LE01, RE01>: Value,
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E01: Value,
+    E02: Value,
+    E12: Value,
+    E012: Value,
+{
+    /// Reverses the order of the basis vectors in every blade, negating grades 2 and 3
+    #[inline]
+    pub fn reverse(self) -> GenericMultiVector<S, E0, E1, E2, NO<E01>, NO<E02>, NO<E12>, NO<E012>>
+    where
+        E01: Neg,
+        E02: Neg,
+        E12: Neg,
+        E012: Neg,
+        NO<E01>: Value,
+        NO<E02>: Value,
+        NO<E12>: Value,
+        NO<E012>: Value,
+    {
+        GenericMultiVector {
+            s: self.s,
+            e0: self.e0,
+            e1: self.e1,
+            e2: self.e2,
+            e01: -self.e01,
+            e02: -self.e02,
+            e12: -self.e12,
+            e012: -self.e012,
+        }
+    }
+
+    /// Negates the odd grades 1 and 3
+    #[inline]
+    pub fn grade_involution(
+        self,
+    ) -> GenericMultiVector<S, NO<E0>, NO<E1>, NO<E2>, E01, E02, E12, NO<E012>>
+    where
+        E0: Neg,
+        E1: Neg,
+        E2: Neg,
+        E012: Neg,
+        NO<E0>: Value,
+        NO<E1>: Value,
+        NO<E2>: Value,
+        NO<E012>: Value,
+    {
+        GenericMultiVector {
+            s: self.s,
+            e0: -self.e0,
+            e1: -self.e1,
+            e2: -self.e2,
+            e01: self.e01,
+            e02: self.e02,
+            e12: self.e12,
+            e012: -self.e012,
+        }
+    }
+
+    /// The reverse of the grade involution, negating grades 1 and 2
+    #[inline]
+    pub fn clifford_conjugate(
+        self,
+    ) -> GenericMultiVector<S, NO<E0>, NO<E1>, NO<E2>, NO<E01>, NO<E02>, NO<E12>, E012>
+    where
+        E0: Neg,
+        E1: Neg,
+        E2: Neg,
+        E01: Neg,
+        E02: Neg,
+        E12: Neg,
+        NO<E0>: Value,
+        NO<E1>: Value,
+        NO<E2>: Value,
+        NO<E01>: Value,
+        NO<E02>: Value,
+        NO<E12>: Value,
+    {
+        GenericMultiVector {
+            s: self.s,
+            e0: -self.e0,
+            e1: -self.e1,
+            e2: -self.e2,
+            e01: -self.e01,
+            e02: -self.e02,
+            e12: -self.e12,
+            e012: self.e012,
+        }
+    }
+
+    /// The Poincaré dual (right complement), so that `x ^ x.dual()` is a multiple of `e012`
+    ///
+    /// This maps points to lines and lines to points, and the regressive product is
+    /// `(a.dual() ^ b.dual()).dual()`
+    #[inline]
+    pub fn dual(self) -> GenericMultiVector<E012, E12, NO<E02>, E01, E2, NO<E1>, E0, S>
+    where
+        E1: Neg,
+        E02: Neg,
+        NO<E1>: Value,
+        NO<E02>: Value,
+    {
+        GenericMultiVector {
+            s: self.e012,
+            e0: self.e12,
+            e1: -self.e02,
+            e2: self.e01,
+            e01: self.e2,
+            e02: -self.e1,
+            e12: self.e0,
+            e012: self.s,
+        }
+    }
+
+    /// The Hodge dual, so that `a ^ b.hodge_dual()` is `(a | b.reverse()) * e012` for blades of the
+    /// same grade, where the reverse only matters for `e12`, which squares to -1
+    ///
+    /// Because `e0` squares to zero every component containing `e0` is lost
+    #[inline]
+    pub fn hodge_dual(self) -> GenericMultiVector<Zero, E12, Zero, Zero, E2, NO<E1>, Zero, S>
+    where
+        E1: Neg,
+        NO<E1>: Value,
+    {
+        GenericMultiVector {
+            s: Zero,
+            e0: self.e12,
+            e1: Zero,
+            e2: Zero,
+            e01: self.e2,
+            e02: -self.e1,
+            e12: Zero,
+            e012: self.s,
+        }
+    }
+
+    /// The scalar part
+    #[inline]
+    pub fn grade0(self) -> GenericMultiVector<S, Zero, Zero, Zero, Zero, Zero, Zero, Zero> {
+        GenericMultiVector {
+            s: self.s,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: Zero,
+            e02: Zero,
+            e12: Zero,
+            e012: Zero,
+        }
+    }
+
+    /// The vector part, a [`Vector`] or [`Line`]
+    #[inline]
+    pub fn grade1(self) -> GenericMultiVector<Zero, E0, E1, E2, Zero, Zero, Zero, Zero> {
+        GenericMultiVector {
+            s: Zero,
+            e0: self.e0,
+            e1: self.e1,
+            e2: self.e2,
+            e01: Zero,
+            e02: Zero,
+            e12: Zero,
+            e012: Zero,
+        }
+    }
+
+    /// The bivector part, a [`BiVector`] or [`Point`]
+    #[inline]
+    pub fn grade2(self) -> GenericMultiVector<Zero, Zero, Zero, Zero, E01, E02, E12, Zero> {
+        GenericMultiVector {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: self.e01,
+            e02: self.e02,
+            e12: self.e12,
+            e012: Zero,
+        }
+    }
+
+    /// The trivector part, the multiple of the pseudoscalar `e012`
+    #[inline]
+    pub fn grade3(self) -> GenericMultiVector<Zero, Zero, Zero, Zero, Zero, Zero, Zero, E012> {
+        GenericMultiVector {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: Zero,
+            e02: Zero,
+            e12: Zero,
+            e012: self.e012,
+        }
+    }
+
+    /// The norm of the components that contain `e0`
+    ///
+    /// For a normalized point or line this is its distance to the origin
+    #[inline]
+    pub fn bulk_norm<T>(self) -> T
+    where
+        T: Real,
+        E0: Component<T>,
+        E01: Component<T>,
+        E02: Component<T>,
+        E012: Component<T>,
+    {
+        let e0 = self.e0.get();
+        let e01 = self.e01.get();
+        let e02 = self.e02.get();
+        let e012 = self.e012.get();
+        (e0 * e0 + e01 * e01 + e02 * e02 + e012 * e012).sqrt()
+    }
+
+    /// The norm of the components that don't contain `e0`
+    ///
+    /// This is the `e12` component of a point, and the length of the normal of a line
+    #[inline]
+    pub fn weight_norm<T>(self) -> T
+    where
+        T: Real,
+        S: Component<T>,
+        E1: Component<T>,
+        E2: Component<T>,
+        E12: Component<T>,
+    {
+        let s = self.s.get();
+        let e1 = self.e1.get();
+        let e2 = self.e2.get();
+        let e12 = self.e12.get();
+        (s * s + e1 * e1 + e2 * e2 + e12 * e12).sqrt()
+    }
+
+    /// Scales `self` so that its [`weight_norm`](Self::weight_norm) is 1
+    #[inline]
+    pub fn normalized<T>(self) -> Self
+    where
+        T: Real,
+        // the `Component` bounds pin down `T`, so they have to come before the `Mul` ones, which
+        // can otherwise send inference off into the scalar times multivector impls
+        S: Component<T> + Mul<T, Output = S>,
+        E1: Component<T> + Mul<T, Output = E1>,
+        E2: Component<T> + Mul<T, Output = E2>,
+        E12: Component<T> + Mul<T, Output = E12>,
+        E0: Mul<T, Output = E0>,
+        E01: Mul<T, Output = E01>,
+        E02: Mul<T, Output = E02>,
+        E012: Mul<T, Output = E012>,
+    {
+        let scale = T::ONE / self.weight_norm();
+        Self {
+            s: self.s * scale,
+            e0: self.e0 * scale,
+            e1: self.e1 * scale,
+            e2: self.e2 * scale,
+            e01: self.e01 * scale,
+            e02: self.e02 * scale,
+            e12: self.e12 * scale,
+            e012: self.e012 * scale,
+        }
+    }
+}
+
+impl<T, S, E0, E1, E2, E01, E02, E12, E012> Components<T>
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    T: Number,
+    S: Component<T>,
+    E0: Component<T>,
+    E1: Component<T>,
+    E2: Component<T>,
+    E01: Component<T>,
+    E02: Component<T>,
+    E12: Component<T>,
+    E012: Component<T>,
+{
+    #[inline]
+    fn components(self) -> [T; 8] {
+        [
+            self.s.get(),
+            self.e0.get(),
+            self.e1.get(),
+            self.e2.get(),
+            self.e01.get(),
+            self.e02.get(),
+            self.e12.get(),
+            self.e012.get(),
+        ]
+    }
+}
+
+impl<S, E0, E1, E2, E01, E02, E12, E012> GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E01: Value,
+    E02: Value,
+    E12: Value,
+    E012: Value,
+{
+    /// Whether every component differs from the one in `other` by at most `epsilon`
+    ///
+    /// `other` can have [`Zero`] components in different places, e.g. the result of a product can
+    /// be compared with a [`Point`]
+    #[inline]
+    pub fn approx_eq<T: Real>(self, other: impl Components<T>, epsilon: T) -> bool
+    where
+        Self: Components<T>,
+    {
+        self.all_components(other, |a, b| (a - b).abs() <= epsilon)
+    }
+
+    /// Whether every component differs from the one in `other` by at most `max_relative` times
+    /// the larger magnitude of the two
+    #[inline]
+    pub fn approx_eq_relative<T: Real>(self, other: impl Components<T>, max_relative: T) -> bool
+    where
+        Self: Components<T>,
+    {
+        self.all_components(other, |a, b| {
+            let largest = if a.abs() > b.abs() { a.abs() } else { b.abs() };
+            (a - b).abs() <= largest * max_relative
+        })
+    }
+
+    /// Whether every component is at most `max_ulps` representable values away from the one in
+    /// `other`
+    #[inline]
+    pub fn approx_eq_ulps<T: Real>(self, other: impl Components<T>, max_ulps: u64) -> bool
+    where
+        Self: Components<T>,
+    {
+        self.all_components(other, |a, b| a.ulps(b) <= max_ulps)
+    }
+
+    #[inline]
+    fn all_components<T: Number>(self, other: impl Components<T>, f: impl Fn(T, T) -> bool) -> bool
+    where
+        Self: Components<T>,
+    {
+        self.components()
+            .into_iter()
+            .zip(other.components())
+            .all(|(a, b)| f(a, b))
+    }
+}
+
+type AO<A, B> = <A as Add<B>>::Output;
+
+impl<LS, LE0, LE1, LE2, LE01, LE02, LE12, LE012, RS, RE0, RE1, RE2, RE01, RE02, RE12, RE012>
+    Add<GenericMultiVector<RS, RE0, RE1, RE2, RE01, RE02, RE12, RE012>>
+    for GenericMultiVector<LS, LE0, LE1, LE2, LE01, LE02, LE12, LE012>
+where
+    LS: Value + Add<RS>,
+    LE0: Value + Add<RE0>,
+    LE1: Value + Add<RE1>,
+    LE2: Value + Add<RE2>,
+    LE01: Value + Add<RE01>,
+    LE02: Value + Add<RE02>,
+    LE12: Value + Add<RE12>,
+    LE012: Value + Add<RE012>,
+    RS: Value,
+    RE0: Value,
+    RE1: Value,
+    RE2: Value,
+    RE01: Value,
+    RE02: Value,
+    RE12: Value,
+    RE012: Value,
+    AO<LS, RS>: Value,
+    AO<LE0, RE0>: Value,
+    AO<LE1, RE1>: Value,
+    AO<LE2, RE2>: Value,
+    AO<LE01, RE01>: Value,
     AO<LE02, RE02>: Value,
     AO<LE12, RE12>: Value,
     AO<LE012, RE012>: Value,
@@ -322,232 +1497,542 @@ where
     }
 }
 
-/*
-(a + b*e0 + c*e1 + d*e2 + e*e01 + f*e02 + g*e12 + h*e012)(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-
-a*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-+ b*e0*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-+ c*e1*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-+ d*e2*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-+ e*e01*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-+ f*e02*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-+ g*e12*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-+ h*e012*(i + j*e0 + k*e1 + l*e2 + m*e01 + n*e02 + o*e12 + p*e012)
-
-a*i + a*j*e0 + a*k*e1 + a*l*e2 + a*m*e01 + a*n*e02 + a*o*e12 + a*p*e012
-+ b*e0*i + b*e0*j*e0 + b*e0*k*e1 + b*e0*l*e2 + b*e0*m*e01 + b*e0*n*e02 + b*e0*o*e12 + b*e0*p*e012
-+ c*e1*i + c*e1*j*e0 + c*e1*k*e1 + c*e1*l*e2 + c*e1*m*e01 + c*e1*n*e02 + c*e1*o*e12 + c*e1*p*e012
-+ d*e2*i + d*e2*j*e0 + d*e2*k*e1 + d*e2*l*e2 + d*e2*m*e01 + d*e2*n*e02 + d*e2*o*e12 + d*e2*p*e012
-+ e*e01*i + e*e01*j*e0 + e*e01*k*e1 + e*e01*l*e2 + e*e01*m*e01 + e*e01*n*e02 + e*e01*o*e12 + e*e01*p*e012
-+ f*e02*i + f*e02*j*e0 + f*e02*k*e1 + f*e02*l*e2 + f*e02*m*e01 + f*e02*n*e02 + f*e02*o*e12 + f*e02*p*e012
-+ g*e12*i + g*e12*j*e0 + g*e12*k*e1 + g*e12*l*e2 + g*e12*m*e01 + g*e12*n*e02 + g*e12*o*e12 + g*e12*p*e012
-+ h*e012*i + h*e012*j*e0 + h*e012*k*e1 + h*e012*l*e2 + h*e012*m*e01 + h*e012*n*e02 + h*e012*o*e12 + h*e012*p*e012
-
-a*i + a*j*e0 + a*k*e1 + a*l*e2 + a*m*e01 + a*n*e02 + a*o*e12 + a*p*e012
-+ b*e0*i + b*e0*k*e1 + b*e0*l*e2 + b*e0*o*e12
-+ c*e1*i + c*e1*j*e0 + c*e1*k*e1 + c*e1*l*e2 + c*e1*m*e01 + c*e1*n*e02 + c*e1*o*e12 + c*e1*p*e012
-+ d*e2*i + d*e2*j*e0 + d*e2*k*e1 + d*e2*l*e2 + d*e2*m*e01 + d*e2*n*e02 + d*e2*o*e12 + d*e2*p*e012
-+ e*e01*i + e*e01*k*e1 + e*e01*l*e2 + e*e01*o*e12
-+ f*e02*i + f*e02*k*e1 + f*e02*l*e2 + f*e02*o*e12
-+ g*e12*i + g*e12*j*e0 + g*e12*k*e1 + g*e12*l*e2 + g*e12*m*e01 + g*e12*n*e02 + g*e12*o*e12 + g*e12*p*e012
-+ h*e012*i + h*e012*k*e1 + h*e012*l*e2 + h*e012*o*e12
-
-a*i + a*j*e0 + a*k*e1 + a*l*e2 + a*m*e01 + a*n*e02 + a*o*e12 + a*p*e012
-+ b*i*e0 + b*k*e01 + b*l*e02 + b*o*e012
-+ c*i*e1 - c*j*e01 + c*k + c*l*e12 - c*m*e0 - c*n*e012 + c*o*e2 - c*p*e02
-+ d*i*e2 - d*j*e02 - d*k*e12 + d*l + d*m*e012 - d*n*e0 - d*o*e1 + d*p*e01
-+ e*i*e01 + e*k*e0 + e*l*e012 + e*o*e2
-+ f*i*e02 - f*k*e012 + f*l*e0 - f*o*e01
-+ g*i*e12 + g*j*e012 - g*k*e2 + g*l*e1 - g*m*e02 + g*n*e1 - g*o - g*p*e0
-+ h*i*e012 - h*k*e02 + h*l*e01 - h*o*e0
-
-(a*i + c*k + d*l - g*o)
-+ (a*j + b*i - c*m - d*n + e*k + f*l + g*p + h*o)*e0
-+ (a*k + c*i - d*o + g*l + g*n)*e1
-+ (a*l + d*i + c*o + d*i + e*o - g*k)*e2
-+ (a*m + b*k - c*j + d*p + e*i - f*o + h*l)*e01
-+ (a*n + b*l - d*j - c*p + f*i - g*m - h*k)*e02
-+ (a*o + c*l - d*k + g*i)*e12
-+ (a*p + b*p - c*n + d*m + e*l - f*k + g*j + h*i)*e012
-*/
+// these only exist when the result has the same type, e.g. a `Motor` can be multiplied by a
+// `Motor` in place but not by a `Point`
+impl<Rhs, S, E0, E1, E2, E01, E02, E12, E012> AddAssign<Rhs>
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E01: Value,
+    E02: Value,
+    E12: Value,
+    E012: Value,
+    Self: Add<Rhs, Output = Self>,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Rhs) {
+        *self = *self + rhs;
+    }
+}
 
-type MO<A, B> = <A as Mul<B>>::Output;
+impl<Rhs, S, E0, E1, E2, E01, E02, E12, E012> SubAssign<Rhs>
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E01: Value,
+    E02: Value,
+    E12: Value,
+    E012: Value,
+    Self: Sub<Rhs, Output = Self>,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Rhs) {
+        *self = *self - rhs;
+    }
+}
 
-impl<A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P> Mul<GenericMultiVector<I, J, K, L, M, N, O, P>>
-    for GenericMultiVector<A, B, C, D, E, F, G, H>
+impl<Rhs, S, E0, E1, E2, E01, E02, E12, E012> MulAssign<Rhs>
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
 where
-    A: Value + Mul<I> + Mul<J> + Mul<K> + Mul<L> + Mul<M> + Mul<N> + Mul<O> + Mul<P>,
-    B: Value + Mul<I> + Mul<K> + Mul<L> + Mul<P>,
-    C: Value + Mul<K> + Mul<M> + Mul<I> + Mul<O> + Mul<J> + Mul<P> + Mul<L> + Mul<N>,
-    D: Value + Mul<L> + Mul<N> + Mul<O> + Mul<I> + Mul<P> + Mul<J> + Mul<K> + Mul<M>,
-    E: Value + Mul<K> + Mul<O> + Mul<I> + Mul<L>,
-    F: Value + Mul<L> + Mul<O> + Mul<I> + Mul<K>,
-    G: Value + Mul<O> + Mul<P> + Mul<L> + Mul<N> + Mul<K> + Mul<M> + Mul<I> + Mul<J>,
-    H: Value + Mul<O> + Mul<L> + Mul<K> + Mul<I>,
-    I: Value,
-    J: Value,
-    K: Value,
-    L: Value,
-    M: Value,
-    N: Value,
-    O: Value,
-    P: Value,
-    MO<A, I>: Add<MO<C, K>>,
-    MO<A, J>: Add<MO<B, I>>,
-    MO<A, K>: Add<MO<C, I>>,
-    MO<A, L>: Add<MO<D, I>>,
-    MO<A, M>: Add<MO<B, K>>,
-    MO<A, N>: Add<MO<B, L>>,
-    MO<A, O>: Add<MO<C, L>>,
-    MO<A, P>: Add<MO<B, P>>,
-    AO<MO<A, I>, MO<C, K>>: Add<MO<D, L>>,
-    AO<MO<A, J>, MO<B, I>>: Sub<MO<C, M>>,
-    AO<MO<A, K>, MO<C, I>>: Sub<MO<D, O>>,
-    AO<MO<A, L>, MO<D, I>>: Add<MO<C, O>>,
-    AO<MO<A, M>, MO<B, K>>: Sub<MO<C, J>>,
-    AO<MO<A, N>, MO<B, L>>: Sub<MO<D, J>>,
-    AO<MO<A, O>, MO<C, L>>: Sub<MO<D, K>>,
-    AO<MO<A, P>, MO<B, P>>: Sub<MO<C, N>>,
-    AO<AO<MO<A, I>, MO<C, K>>, MO<D, L>>: Sub<MO<G, O>>,
-    SO<AO<MO<A, J>, MO<B, I>>, MO<C, M>>: Sub<MO<D, N>>,
-    SO<AO<MO<A, K>, MO<C, I>>, MO<D, O>>: Add<MO<G, L>>,
-    AO<AO<MO<A, L>, MO<D, I>>, MO<C, O>>: Add<MO<D, I>>,
-    SO<AO<MO<A, M>, MO<B, K>>, MO<C, J>>: Add<MO<D, P>>,
-    SO<AO<MO<A, N>, MO<B, L>>, MO<D, J>>: Sub<MO<C, P>>,
-    SO<AO<MO<A, O>, MO<C, L>>, MO<D, K>>: Add<MO<G, I>>,
-    SO<AO<MO<A, P>, MO<B, P>>, MO<C, N>>: Add<MO<D, M>>,
-    SO<AO<AO<MO<A, I>, MO<C, K>>, MO<D, L>>, MO<G, O>>: Value,
-    SO<SO<AO<MO<A, J>, MO<B, I>>, MO<C, M>>, MO<D, N>>: Add<MO<E, K>>,
-    AO<SO<AO<MO<A, K>, MO<C, I>>, MO<D, O>>, MO<G, L>>: Add<MO<G, N>>,
-    AO<AO<AO<MO<A, L>, MO<D, I>>, MO<C, O>>, MO<D, I>>: Add<MO<E, O>>,
-    AO<SO<AO<MO<A, M>, MO<B, K>>, MO<C, J>>, MO<D, P>>: Add<MO<E, I>>,
-    SO<SO<AO<MO<A, N>, MO<B, L>>, MO<D, J>>, MO<C, P>>: Add<MO<F, I>>,
-    AO<SO<AO<MO<A, O>, MO<C, L>>, MO<D, K>>, MO<G, I>>: Value,
-    AO<SO<AO<MO<A, P>, MO<B, P>>, MO<C, N>>, MO<D, M>>: Add<MO<E, L>>,
-    AO<SO<SO<AO<MO<A, J>, MO<B, I>>, MO<C, M>>, MO<D, N>>, MO<E, K>>: Add<MO<F, L>>,
-    AO<AO<SO<AO<MO<A, K>, MO<C, I>>, MO<D, O>>, MO<G, L>>, MO<G, N>>: Value,
-    AO<AO<AO<AO<MO<A, L>, MO<D, I>>, MO<C, O>>, MO<D, I>>, MO<E, O>>: Sub<MO<G, K>>,
-    AO<AO<SO<AO<MO<A, M>, MO<B, K>>, MO<C, J>>, MO<D, P>>, MO<E, I>>: Sub<MO<F, O>>,
-    AO<SO<SO<AO<MO<A, N>, MO<B, L>>, MO<D, J>>, MO<C, P>>, MO<F, I>>: Sub<MO<G, M>>,
-    AO<AO<SO<AO<MO<A, P>, MO<B, P>>, MO<C, N>>, MO<D, M>>, MO<E, L>>: Sub<MO<F, K>>,
-    AO<AO<SO<SO<AO<MO<A, J>, MO<B, I>>, MO<C, M>>, MO<D, N>>, MO<E, K>>, MO<F, L>>: Add<MO<G, P>>,
-    SO<AO<AO<AO<AO<MO<A, L>, MO<D, I>>, MO<C, O>>, MO<D, I>>, MO<E, O>>, MO<G, K>>: Value,
-    SO<AO<SO<SO<AO<MO<A, N>, MO<B, L>>, MO<D, J>>, MO<C, P>>, MO<F, I>>, MO<G, M>>: Sub<MO<H, K>>,
-    SO<AO<AO<SO<AO<MO<A, M>, MO<B, K>>, MO<C, J>>, MO<D, P>>, MO<E, I>>, MO<F, O>>: Add<MO<H, L>>,
-    SO<AO<AO<SO<AO<MO<A, P>, MO<B, P>>, MO<C, N>>, MO<D, M>>, MO<E, L>>, MO<F, K>>: Add<MO<G, J>>,
-    AO<AO<AO<SO<SO<AO<MO<A, J>, MO<B, I>>, MO<C, M>>, MO<D, N>>, MO<E, K>>, MO<F, L>>, MO<G, P>>:
-        Add<MO<H, O>>,
-    AO<SO<AO<AO<SO<AO<MO<A, M>, MO<B, K>>, MO<C, J>>, MO<D, P>>, MO<E, I>>, MO<F, O>>, MO<H, L>>:
-        Value,
-    SO<SO<AO<SO<SO<AO<MO<A, N>, MO<B, L>>, MO<D, J>>, MO<C, P>>, MO<F, I>>, MO<G, M>>, MO<H, K>>:
-        Value,
-    AO<SO<AO<AO<SO<AO<MO<A, P>, MO<B, P>>, MO<C, N>>, MO<D, M>>, MO<E, L>>, MO<F, K>>, MO<G, J>>:
-        Add<MO<H, I>>,
-    AO<
-        AO<
-            AO<AO<SO<SO<AO<MO<A, J>, MO<B, I>>, MO<C, M>>, MO<D, N>>, MO<E, K>>, MO<F, L>>,
-            MO<G, P>,
-        >,
-        MO<H, O>,
-    >: Value,
-    AO<
-        AO<
-            SO<AO<AO<SO<AO<MO<A, P>, MO<B, P>>, MO<C, N>>, MO<D, M>>, MO<E, L>>, MO<F, K>>,
-            MO<G, J>,
-        >,
-        MO<H, I>,
-    >: Value,
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E01: Value,
+    E02: Value,
+    E12: Value,
+    E012: Value,
+    Self: Mul<Rhs, Output = Self>,
 {
-    type Output = GenericMultiVector<
-        SO<AO<AO<MO<A, I>, MO<C, K>>, MO<D, L>>, MO<G, O>>,
-        AO<
-            AO<
-                AO<AO<SO<SO<AO<MO<A, J>, MO<B, I>>, MO<C, M>>, MO<D, N>>, MO<E, K>>, MO<F, L>>,
-                MO<G, P>,
-            >,
-            MO<H, O>,
-        >,
-        AO<AO<SO<AO<MO<A, K>, MO<C, I>>, MO<D, O>>, MO<G, L>>, MO<G, N>>,
-        SO<AO<AO<AO<AO<MO<A, L>, MO<D, I>>, MO<C, O>>, MO<D, I>>, MO<E, O>>, MO<G, K>>,
-        AO<
-            SO<AO<AO<SO<AO<MO<A, M>, MO<B, K>>, MO<C, J>>, MO<D, P>>, MO<E, I>>, MO<F, O>>,
-            MO<H, L>,
-        >,
-        SO<
-            SO<AO<SO<SO<AO<MO<A, N>, MO<B, L>>, MO<D, J>>, MO<C, P>>, MO<F, I>>, MO<G, M>>,
-            MO<H, K>,
-        >,
-        AO<SO<AO<MO<A, O>, MO<C, L>>, MO<D, K>>, MO<G, I>>,
-        AO<
-            AO<
-                SO<AO<AO<SO<AO<MO<A, P>, MO<B, P>>, MO<C, N>>, MO<D, M>>, MO<E, L>>, MO<F, K>>,
-                MO<G, J>,
-            >,
-            MO<H, I>,
-        >,
-    >;
+    #[inline]
+    fn mul_assign(&mut self, rhs: Rhs) {
+        *self = *self * rhs;
+    }
+}
 
+impl<Rhs, S, E0, E1, E2, E01, E02, E12, E012> DivAssign<Rhs>
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E01: Value,
+    E02: Value,
+    E12: Value,
+    E012: Value,
+    Self: Div<Rhs, Output = Self>,
+{
     #[inline]
-    fn mul(self, rhs: GenericMultiVector<I, J, K, L, M, N, O, P>) -> Self::Output {
-        let a = self.s;
-        let b = self.e0;
-        let c = self.e1;
-        let d = self.e2;
-        let e = self.e01;
-        let f = self.e02;
-        let g = self.e12;
-        let h = self.e012;
-        let i = rhs.s;
-        let j = rhs.e0;
-        let k = rhs.e1;
-        let l = rhs.e2;
-        let m = rhs.e01;
-        let n = rhs.e02;
-        let o = rhs.e12;
-        let p = rhs.e012;
-        Self::Output {
-            s: a * i + c * k + d * l - g * o,
-            e0: a * j + b * i - c * m - d * n + e * k + f * l + g * p + h * o,
-            e1: a * k + c * i - d * o + g * l + g * n,
-            e2: a * l + d * i + c * o + d * i + e * o - g * k,
-            e01: a * m + b * k - c * j + d * p + e * i - f * o + h * l,
-            e02: a * n + b * l - d * j - c * p + f * i - g * m - h * k,
-            e12: a * o + c * l - d * k + g * i,
-            e012: a * p + b * p - c * n + d * m + e * l - f * k + g * j + h * i,
-        }
+    fn div_assign(&mut self, rhs: Rhs) {
+        *self = *self / rhs;
     }
 }
 
-#[test]
-fn test() {
-    let a = MultiVector {
-        s: 2.0,
-        e0: 3.0,
-        e1: 5.0,
-        e2: 7.0,
-        e01: 11.0,
-        e02: 13.0,
-        e12: 17.0,
-        e012: 19.0,
-    };
-    let b = MultiVector {
-        s: 23.0,
-        e0: 29.0,
-        e1: 31.0,
-        e2: 37.0,
-        e01: 41.0,
-        e02: 43.0,
-        e12: 47.0,
-        e012: 53.0,
+type MO<A, B> = <A as Mul<B>>::Output;
+type DO<A, B> = <A as Div<B>>::Output;
+
+// the geometric, outer, regressive and inner products and the contractions, see `build.rs`
+include!(concat!(env!("OUT_DIR"), "/multivector_products.rs"));
+
+impl<T: Number> Motor<T> {
+    pub const IDENTITY: Self = Self {
+        s: T::ONE,
+        e0: Zero,
+        e1: Zero,
+        e2: Zero,
+        e01: T::ZERO,
+        e02: T::ZERO,
+        e12: T::ZERO,
+        e012: Zero,
     };
-    assert_eq!(
-        a * b,
-        MultiVector {
-            // -339, -1351, 477, -57, 1477, -741, 453, 1253
-            s: -339.0,
-            e0: -1351.0,
-            e1: 477.0,
-            e2: -57.0,
+
+    /// Moves everything by `x` and `y`
+    #[inline]
+    pub fn translation(x: T, y: T) -> Self {
+        Self {
+            s: T::ONE,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: -(T::HALF * x),
+            e02: -(T::HALF * y),
+            e12: T::ZERO,
+            e012: Zero,
+        }
+    }
+
+    /// Returns the motor that applies `self` and then `next`
+    #[inline]
+    pub fn then(self, next: Self) -> Self {
+        next * self
+    }
+
+    /// Applies the motor to `value` with the sandwich product `self * value * self.reverse()`
+    #[inline]
+    pub fn transform<V: Sandwich<T>>(self, value: V) -> V {
+        value.sandwich(self)
+    }
+}
+
+impl<T: Real> Motor<T> {
+    /// Rotates everything counter-clockwise by `angle` radians around the origin
+    #[inline]
+    pub fn rotation(angle: T) -> Self {
+        let (sin, cos) = (T::HALF * angle).sin_cos();
+        Self {
+            s: cos,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: T::ZERO,
+            e02: T::ZERO,
+            e12: -sin,
+            e012: Zero,
+        }
+    }
+
+    /// Rotates by `angle` radians around the origin and then moves by `x` and `y`
+    #[inline]
+    pub fn from_cartesian(x: T, y: T, angle: T) -> Self {
+        Self::translation(x, y) * Self::rotation(angle)
+    }
+
+    /// The inverse of [`Motor::from_cartesian`], returns `(x, y, angle)`
+    #[inline]
+    pub fn to_cartesian(self) -> (T, T, T) {
+        let origin = self.transform(Point {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: T::ZERO,
+            e02: T::ZERO,
+            e12: T::ONE,
+            e012: Zero,
+        });
+        let half_angle = T::atan2(-self.e12, self.s);
+        (
+            -origin.e02 / origin.e12,
+            origin.e01 / origin.e12,
+            half_angle + half_angle,
+        )
+    }
+
+    /// The bivector `b` such that `b.exp() == self`, taking the shortest path
+    ///
+    /// `self` is expected to be normalized
+    #[inline]
+    pub fn log(self) -> BiVector<T> {
+        // `self` and `-self` are the same transform, pick the one with the smaller angle
+        let motor = if self.s < T::ZERO { -self } else { self };
+        let angle = T::atan2(motor.e12, motor.s);
+        let scale = if angle.abs() < T::EPSILON {
+            T::ONE
+        } else {
+            angle / angle.sin_cos().0
+        };
+        BiVector {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: motor.e01 * scale,
+            e02: motor.e02 * scale,
+            e12: motor.e12 * scale,
+            e012: Zero,
+        }
+    }
+
+    /// Normalized linear interpolation from `self` at `t = 0` to `other` at `t = 1`
+    ///
+    /// This is cheaper than [`Motor::slerp`] but doesn't move at a constant speed
+    #[inline]
+    pub fn lerp(self, other: Self, t: T) -> Self {
+        let other = if self.s * other.s + self.e12 * other.e12 < T::ZERO {
+            -other
+        } else {
+            other
+        };
+        let scale = |motor: Self, scale: T| Motor {
+            s: motor.s * scale,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: motor.e01 * scale,
+            e02: motor.e02 * scale,
+            e12: motor.e12 * scale,
+            e012: Zero,
+        };
+        (scale(self, T::ONE - t) + scale(other, t)).normalized()
+    }
+
+    /// Interpolates along the screw motion from `self` at `t = 0` to `other` at `t = 1`
+    #[inline]
+    pub fn slerp(self, other: Self, t: T) -> Self {
+        let delta = (other * self.reverse()).log();
+        let delta = BiVector {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: delta.e01 * t,
+            e02: delta.e02 * t,
+            e12: delta.e12 * t,
+            e012: Zero,
+        };
+        delta.exp() * self
+    }
+}
+
+impl<T: Real> BiVector<T> {
+    /// The exponential map, turning a bivector into the [`Motor`] it generates
+    ///
+    /// `e12` rotates around the origin by `-2 * e12` radians and `e01`/`e02` translate by
+    /// `-2 * e01`/`-2 * e02`, a bivector without `e12` gives a pure translation
+    #[inline]
+    pub fn exp(self) -> Motor<T> {
+        // the bivector squares to `-e12 * e12`, so this is just like complex numbers
+        let angle = self.e12;
+        let (sin, cos) = angle.sin_cos();
+        let scale = if angle.abs() < T::EPSILON {
+            T::ONE
+        } else {
+            sin / angle
+        };
+        Motor {
+            s: cos,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: self.e01 * scale,
+            e02: self.e02 * scale,
+            e12: sin,
+            e012: Zero,
+        }
+    }
+}
+
+pub trait Sandwich<T: Number> {
+    fn sandwich(self, motor: Motor<T>) -> Self;
+}
+
+impl<T: Number> Sandwich<T> for Point<T> {
+    #[inline]
+    fn sandwich(self, motor: Motor<T>) -> Self {
+        let result = motor * self * motor.reverse();
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: result.e01,
+            e02: result.e02,
+            e12: result.e12,
+            e012: Zero,
+        }
+    }
+}
+
+impl<T: Number> Sandwich<T> for Line<T> {
+    #[inline]
+    fn sandwich(self, motor: Motor<T>) -> Self {
+        let result = motor * self * motor.reverse();
+        Self {
+            s: Zero,
+            e0: result.e0,
+            e1: result.e1,
+            e2: result.e2,
+            e01: Zero,
+            e02: Zero,
+            e12: Zero,
+            e012: Zero,
+        }
+    }
+}
+
+impl<T: Number> Sandwich<T> for Motor<T> {
+    #[inline]
+    fn sandwich(self, motor: Motor<T>) -> Self {
+        motor * self * motor.reverse()
+    }
+}
+
+impl<T: Number> Point<T> {
+    /// The point at `x`, `y` with a weight of 1
+    #[inline]
+    pub fn new(x: T, y: T) -> Self {
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: y,
+            e02: -x,
+            e12: T::ONE,
+            e012: Zero,
+        }
+    }
+
+    /// The ideal point (point at infinity) in the direction of `x`, `y`
+    #[inline]
+    pub fn direction(x: T, y: T) -> Self {
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: y,
+            e02: -x,
+            e12: T::ZERO,
+            e012: Zero,
+        }
+    }
+
+    /// This divides by the weight, so it is only meaningful for points that aren't ideal
+    #[inline]
+    pub fn x(self) -> T {
+        -self.e02 / self.e12
+    }
+
+    /// This divides by the weight, so it is only meaningful for points that aren't ideal
+    #[inline]
+    pub fn y(self) -> T {
+        self.e01 / self.e12
+    }
+}
+
+impl<T: Real> Point<T> {
+    /// Whether this is a point at infinity, like the intersection of two parallel lines
+    #[inline]
+    pub fn is_ideal(self) -> bool {
+        self.e12.abs() <= T::EPSILON
+    }
+}
+
+impl<T: Number> Line<T> {
+    /// The line `a * x + b * y + c = 0`, pointing in the direction `(b, -a)`
+    #[inline]
+    pub fn new(a: T, b: T, c: T) -> Self {
+        Self {
+            s: Zero,
+            e0: c,
+            e1: a,
+            e2: b,
+            e01: Zero,
+            e02: Zero,
+            e12: Zero,
+            e012: Zero,
+        }
+    }
+
+    /// The line going from `a` to `b`, the positive side of it is on the left
+    #[inline]
+    pub fn through(a: Point<T>, b: Point<T>) -> Self {
+        a & b
+    }
+
+    /// The point where `self` and `other` cross, this is an ideal point if they are parallel
+    #[inline]
+    pub fn intersection(self, other: Self) -> Point<T> {
+        self ^ other
+    }
+
+    /// Mirrors `value` across this line, `self` is expected to be normalized
+    #[inline]
+    pub fn reflect<V: Reflect<T>>(self, value: V) -> V {
+        value.reflect(self)
+    }
+}
+
+impl<T: Real> Line<T> {
+    /// The distance from `point` to this line, positive on the side that the normal `(a, b)` points to
+    #[inline]
+    pub fn signed_distance(self, point: Point<T>) -> T {
+        (point & self).s / (point.e12 * self.weight_norm())
+    }
+
+    /// The closest point to `point` on this line
+    #[inline]
+    pub fn project(self, point: Point<T>) -> Point<T> {
+        let perpendicular = self << point;
+        perpendicular ^ self
+    }
+
+    /// The counter-clockwise angle in radians from the direction of `self` to the direction of `other`
+    #[inline]
+    pub fn angle(self, other: Self) -> T {
+        T::atan2((self ^ other).e12, (self | other).s)
+    }
+}
+
+pub trait Reflect<T: Number> {
+    fn reflect(self, line: Line<T>) -> Self;
+}
+
+impl<T: Number> Reflect<T> for Point<T> {
+    #[inline]
+    fn reflect(self, line: Line<T>) -> Self {
+        // negated so that the weight of the point keeps its sign
+        let result = -(line * self * line);
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: result.e01,
+            e02: result.e02,
+            e12: result.e12,
+            e012: Zero,
+        }
+    }
+}
+
+impl<T: Number> Reflect<T> for Line<T> {
+    #[inline]
+    fn reflect(self, line: Line<T>) -> Self {
+        let result = -(line * self * line);
+        Self {
+            s: Zero,
+            e0: result.e0,
+            e1: result.e1,
+            e2: result.e2,
+            e01: Zero,
+            e02: Zero,
+            e12: Zero,
+            e012: Zero,
+        }
+    }
+}
+
+/// Applies `motors[i]` to `points[i]`, [`F32x8::LANES`] entities at a time
+///
+/// # Panics
+///
+/// If `motors` and `points` have different lengths
+pub fn transform_points(motors: &[Motor], points: &mut [Point]) {
+    assert_eq!(motors.len(), points.len());
+    let mut motor_chunks = motors.chunks_exact(F32x8::LANES);
+    let mut point_chunks = points.chunks_exact_mut(F32x8::LANES);
+    for (motors, points) in (&mut motor_chunks).zip(&mut point_chunks) {
+        let motors = Motor::<F32x8>::from_lanes(motors.try_into().unwrap());
+        let batch = Point::<F32x8>::from_lanes(points.as_ref().try_into().unwrap());
+        let batch = motors.transform(batch);
+        for (index, point) in points.iter_mut().enumerate() {
+            *point = batch.lane(index);
+        }
+    }
+    for (motor, point) in motor_chunks
+        .remainder()
+        .iter()
+        .zip(point_chunks.into_remainder())
+    {
+        *point = motor.transform(*point);
+    }
+}
+
+/// Replaces `motors[i]` with `parents[i] * motors[i]`, [`F32x8::LANES`] entities at a time
+///
+/// # Panics
+///
+/// If `parents` and `motors` have different lengths
+pub fn compose_motors(parents: &[Motor], motors: &mut [Motor]) {
+    assert_eq!(parents.len(), motors.len());
+    let mut parent_chunks = parents.chunks_exact(F32x8::LANES);
+    let mut motor_chunks = motors.chunks_exact_mut(F32x8::LANES);
+    for (parents, motors) in (&mut parent_chunks).zip(&mut motor_chunks) {
+        let parents = Motor::<F32x8>::from_lanes(parents.try_into().unwrap());
+        let batch = Motor::<F32x8>::from_lanes(motors.as_ref().try_into().unwrap());
+        let batch = parents * batch;
+        for (index, motor) in motors.iter_mut().enumerate() {
+            *motor = batch.lane(index);
+        }
+    }
+    for (parent, motor) in parent_chunks
+        .remainder()
+        .iter()
+        .zip(motor_chunks.into_remainder())
+    {
+        *motor = *parent * *motor;
+    }
+}
+
+#[test]
+fn test() {
+    let a = MultiVector {
+        s: 2.0,
+        e0: 3.0,
+        e1: 5.0,
+        e2: 7.0,
+        e01: 11.0,
+        e02: 13.0,
+        e12: 17.0,
+        e012: 19.0,
+    };
+    let b = MultiVector {
+        s: 23.0,
+        e0: 29.0,
+        e1: 31.0,
+        e2: 37.0,
+        e01: 41.0,
+        e02: 43.0,
+        e12: 47.0,
+        e012: 53.0,
+    };
+    assert_eq!(
+        a * b,
+        MultiVector {
+            // the same values as `reference_product` in `products_match_reference` gives
+            s: -339.0,
+            e0: -1351.0,
+            e1: 477.0,
+            e2: -57.0,
             e01: 1477.0,
             e02: -741.0,
             e12: 453.0,
@@ -555,3 +2040,631 @@ fn test() {
         }
     );
 }
+
+#[test]
+fn typed_product() {
+    let a: Point = Point {
+        s: Zero,
+        e0: Zero,
+        e1: Zero,
+        e2: Zero,
+        e01: 2.0,
+        e02: 3.0,
+        e12: 1.0,
+        e012: Zero,
+    };
+    let b: Point = Point {
+        s: Zero,
+        e0: Zero,
+        e1: Zero,
+        e2: Zero,
+        e01: 5.0,
+        e02: -1.0,
+        e12: 1.0,
+        e012: Zero,
+    };
+    let c: GenericMultiVector<f32, Zero, Zero, Zero, f32, f32, Zero, Zero> = a * b;
+    assert_eq!(c.s, -1.0);
+    assert_eq!(c.e01, -4.0);
+    assert_eq!(c.e02, -3.0);
+}
+
+#[test]
+fn motor() {
+    fn point(x: f32, y: f32) -> Point {
+        Point {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: y,
+            e02: -x,
+            e12: 1.0,
+            e012: Zero,
+        }
+    }
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} != {b}");
+    }
+
+    let p = Motor::translation(2.0, -1.0).transform(point(1.0, 1.0));
+    assert_close(p.e01, 0.0);
+    assert_close(p.e02, -3.0);
+    assert_close(p.e12, 1.0);
+
+    let p = Motor::rotation(std::f32::consts::FRAC_PI_2).transform(point(1.0, 0.0));
+    assert_close(p.e01, 1.0);
+    assert_close(p.e02, 0.0);
+
+    // x = 1 rotated a quarter turn around the origin is y = 1
+    let line = Line {
+        s: Zero,
+        e0: -1.0,
+        e1: 1.0,
+        e2: 0.0,
+        e01: Zero,
+        e02: Zero,
+        e12: Zero,
+        e012: Zero,
+    };
+    let line = Motor::rotation(std::f32::consts::FRAC_PI_2).transform(line);
+    assert_close(line.e0, -1.0);
+    assert_close(line.e1, 0.0);
+    assert_close(line.e2, 1.0);
+
+    let motor = Motor::from_cartesian(3.0, -2.0, 0.5);
+    let (x, y, angle) = motor.to_cartesian();
+    assert_close(x, 3.0);
+    assert_close(y, -2.0);
+    assert_close(angle, 0.5);
+
+    let composed = Motor::rotation(0.25).then(Motor::translation(1.0, 0.0));
+    let (x, y, angle) = composed.to_cartesian();
+    assert_close(x, 1.0);
+    assert_close(y, 0.0);
+    assert_close(angle, 0.25);
+    let p = composed.transform(point(0.0, 0.0));
+    assert_close(-p.e02 / p.e12, 1.0);
+}
+
+#[test]
+fn meet_and_join() {
+    let origin = Point {
+        s: Zero,
+        e0: Zero,
+        e1: Zero,
+        e2: Zero,
+        e01: 0.0,
+        e02: 0.0,
+        e12: 1.0,
+        e012: Zero,
+    };
+    // (2, 0)
+    let p = Point {
+        s: Zero,
+        e0: Zero,
+        e1: Zero,
+        e2: Zero,
+        e01: 0.0,
+        e02: -2.0,
+        e12: 1.0,
+        e012: Zero,
+    };
+
+    // the x axis, y = 0
+    let line: Line = origin & p;
+    assert_eq!((line.e0, line.e1, line.e2), (0.0, 0.0, 2.0));
+
+    // x = 1
+    let vertical = Line {
+        s: Zero,
+        e0: -1.0,
+        e1: 1.0,
+        e2: 0.0,
+        e01: Zero,
+        e02: Zero,
+        e12: Zero,
+        e012: Zero,
+    };
+    let intersection: Point = line ^ vertical;
+    assert_eq!(-intersection.e02 / intersection.e12, 1.0);
+    assert_eq!(intersection.e01 / intersection.e12, 0.0);
+
+    // perpendicular lines have no angle component
+    let angle: Scalar = line | vertical;
+    assert_eq!(angle.s, 0.0);
+
+    // contracting a point onto a line gives the perpendicular line through the point
+    let perpendicular: Line = line << p;
+    assert_eq!(perpendicular.e2, 0.0);
+    assert_eq!(-perpendicular.e0 / perpendicular.e1, 2.0);
+}
+
+#[test]
+fn unary() {
+    let a = MultiVector {
+        s: 2.0,
+        e0: 3.0,
+        e1: 5.0,
+        e2: 7.0,
+        e01: 11.0,
+        e02: 13.0,
+        e12: 17.0,
+        e012: 19.0,
+    };
+    let b = MultiVector {
+        s: 23.0,
+        e0: 29.0,
+        e1: 31.0,
+        e2: 37.0,
+        e01: 41.0,
+        e02: 43.0,
+        e12: 47.0,
+        e012: 53.0,
+    };
+    assert_eq!((a * b).reverse(), b.reverse() * a.reverse());
+    assert_eq!(
+        (a * b).grade_involution(),
+        a.grade_involution() * b.grade_involution()
+    );
+    assert_eq!(
+        (a * b).clifford_conjugate(),
+        b.clifford_conjugate() * a.clifford_conjugate()
+    );
+    assert_eq!((a & b), (a.dual() ^ b.dual()).dual());
+
+    // (3, 4) with a weight of 2
+    let point = Point {
+        s: Zero,
+        e0: Zero,
+        e1: Zero,
+        e2: Zero,
+        e01: 8.0,
+        e02: -6.0,
+        e12: 2.0,
+        e012: Zero,
+    };
+    assert_eq!(point.weight_norm(), 2.0);
+    let point = point.normalized();
+    assert_eq!(point.e12, 1.0);
+    assert_eq!(point.bulk_norm(), 5.0);
+
+    // 3x + 4y + 10 = 0, which is 2 away from the origin
+    let line = Line {
+        s: Zero,
+        e0: 10.0,
+        e1: 3.0,
+        e2: 4.0,
+        e01: Zero,
+        e02: Zero,
+        e12: Zero,
+        e012: Zero,
+    }
+    .normalized();
+    assert_eq!(line.weight_norm(), 1.0);
+    assert_eq!(line.bulk_norm(), 2.0);
+    let hodge = line.hodge_dual();
+    assert_eq!((line ^ hodge).e012, (line | line).s);
+    let hodge = point.hodge_dual();
+    assert_eq!((point ^ hodge).e012, (point | point.reverse()).s);
+    // unlike vectors, the bivector e12 squares to -1
+    let square: f64 = (point | point).s;
+    assert_eq!((point ^ hodge).e012, -square);
+}
+
+#[test]
+fn motor_interpolation() {
+    fn assert_close(a: Motor, b: Motor) {
+        let difference = a - b;
+        assert!(
+            difference.weight_norm() + difference.bulk_norm() < 1e-5,
+            "{a:?} != {b:?}"
+        );
+    }
+
+    let motors = [
+        Motor::IDENTITY,
+        Motor::translation(3.0, -2.0),
+        Motor::rotation(1.0),
+        Motor::from_cartesian(-1.0, 5.0, -2.5),
+    ];
+    for motor in motors {
+        assert_close(motor.log().exp(), motor);
+    }
+
+    // pure translations must not divide by a zero angle
+    let translation = Motor::translation(4.0, 2.0);
+    let halfway = Motor::IDENTITY.slerp(translation, 0.5);
+    assert_close(halfway, Motor::translation(2.0, 1.0));
+    assert_close(Motor::IDENTITY.lerp(translation, 0.5), halfway);
+
+    let start = Motor::from_cartesian(1.0, 0.0, 0.5);
+    let end = Motor::from_cartesian(-2.0, 3.0, 2.0);
+    assert_close(start.slerp(end, 0.0), start);
+    assert_close(start.slerp(end, 1.0), end);
+    assert_close(start.lerp(end, 0.0), start);
+    assert_close(start.lerp(end, 1.0), end);
+
+    // rotating around a fixed point keeps it fixed for the whole interpolation
+    let around = |angle| {
+        Motor::translation(2.0, 1.0) * Motor::rotation(angle) * Motor::translation(-2.0, -1.0)
+    };
+    let (x, y, angle) = around(0.0).slerp(around(1.5), 0.5).to_cartesian();
+    let expected = around(0.75).to_cartesian();
+    assert!((x - expected.0).abs() < 1e-5);
+    assert!((y - expected.1).abs() < 1e-5);
+    assert!((angle - expected.2).abs() < 1e-5);
+}
+
+#[test]
+fn generic_scalars() {
+    fn translate<T: Number>(x: T, y: T) -> Point<T> {
+        let origin = Point {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e01: T::ZERO,
+            e02: T::ZERO,
+            e12: T::ONE,
+            e012: Zero,
+        };
+        Motor::translation(x, y).transform(origin)
+    }
+
+    let point = translate(3.0f64, -2.0);
+    assert_eq!((-point.e02, point.e01), (3.0, -2.0));
+
+    let point = translate(Fixed::from_int(3), Fixed::from_f32(-2.5));
+    assert_eq!(
+        (-point.e02, point.e01),
+        (Fixed::from_int(3), Fixed::from_f32(-2.5))
+    );
+
+    assert_eq!(
+        std::mem::size_of::<Point<Fixed>>(),
+        3 * std::mem::size_of::<Fixed>()
+    );
+
+    // the same motors with fixed-point math, which gives the same bits on every machine
+    let motor = Motor::from_cartesian(Fixed::from_int(3), Fixed::from_int(-2), Fixed::HALF);
+    let (x, y, angle) = motor.to_cartesian();
+    let epsilon = Fixed::from_f64(1e-8);
+    assert!((x - Fixed::from_int(3)).abs() < epsilon);
+    assert!((y - Fixed::from_int(-2)).abs() < epsilon);
+    assert!((angle - Fixed::HALF).abs() < epsilon);
+    let point = Motor::rotation(Fixed::FRAC_PI_2).transform(Point::new(Fixed::ONE, Fixed::ZERO));
+    assert!(point.approx_eq(Point::new(Fixed::ZERO, Fixed::ONE), epsilon));
+    let halfway = Motor::IDENTITY.slerp(motor, Fixed::HALF).to_cartesian().2;
+    assert!((halfway - Fixed::from_f64(0.25)).abs() < epsilon);
+
+    // degenerate input saturates where floats would give infinities, rather than panicking
+    let ideal = Point::direction(Fixed::ONE, -Fixed::ONE);
+    assert_eq!((ideal.x(), ideal.y()), (Fixed::MAX, Fixed::MIN));
+}
+
+#[test]
+fn products_match_reference() {
+    use crate::reference_algebra::ReferenceAlgebra;
+
+    let algebra = ReferenceAlgebra {
+        blades: [0b000, 0b001, 0b010, 0b100, 0b011, 0b101, 0b110, 0b111],
+        metric: &[0.0, 1.0, 1.0],
+    };
+
+    let to_array = |m: MultiVector<f64>| [m.s, m.e0, m.e1, m.e2, m.e01, m.e02, m.e12, m.e012];
+    let from_array = |[s, e0, e1, e2, e01, e02, e12, e012]: [f64; 8]| MultiVector {
+        s,
+        e0,
+        e1,
+        e2,
+        e01,
+        e02,
+        e12,
+        e012,
+    };
+
+    let values = [
+        [2.0, 3.0, 5.0, 7.0, 11.0, 13.0, 17.0, 19.0],
+        [23.0, 29.0, 31.0, 37.0, 41.0, 43.0, 47.0, 53.0],
+        [-1.0, 0.0, 4.0, -6.0, 2.0, 0.0, -3.0, 8.0],
+        [0.0, -5.0, 1.0, 1.0, -7.0, 9.0, 0.0, -2.0],
+    ];
+    algebra.assert_products_match(&values, to_array, from_array);
+}
+
+#[test]
+fn geometry() {
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} != {b}");
+    }
+
+    let a = Point::new(1.0, 1.0);
+    let b = Point::new(3.0, 1.0);
+    assert_eq!((a.x(), a.y()), (1.0, 1.0));
+
+    // y = 1, pointing right so the positive side is above it
+    let line = Line::through(a, b);
+    assert_close(line.signed_distance(Point::new(-4.0, 3.0)), 2.0);
+    assert_close(line.signed_distance(Point::new(7.0, -0.5)), -1.5);
+
+    let projected = line.project(Point::new(5.0, -2.0));
+    assert_close(projected.x(), 5.0);
+    assert_close(projected.y(), 1.0);
+
+    let reflected = line.normalized().reflect(Point::new(5.0, -2.0));
+    assert_close(reflected.x(), 5.0);
+    assert_close(reflected.y(), 4.0);
+    assert!(reflected.e12 > 0.0);
+
+    // x = y, pointing up and to the right
+    let diagonal = Line::new(-1.0, 1.0, 0.0);
+    let intersection = line.intersection(diagonal);
+    assert!(!intersection.is_ideal());
+    assert_close(intersection.x(), 1.0);
+    assert_close(intersection.y(), 1.0);
+    // reflecting y = 1 across x = y gives x = 1
+    let reflected = diagonal.normalized().reflect(line);
+    assert_close(reflected.signed_distance(Point::new(1.0, 5.0)), 0.0);
+    assert_close(line.angle(diagonal), std::f32::consts::FRAC_PI_4);
+    assert_close(diagonal.angle(line), -std::f32::consts::FRAC_PI_4);
+
+    let parallel = Line::new(0.0, 2.0, 6.0);
+    let intersection = line.intersection(parallel);
+    assert!(intersection.is_ideal());
+    assert_close(line.angle(parallel), 0.0);
+}
+
+#[test]
+fn batches() {
+    let motors = (0..11)
+        .map(|i| Motor::from_cartesian(i as f32, -2.0 * i as f32, 0.3 * i as f32))
+        .collect::<Vec<_>>();
+    let points = (0..11)
+        .map(|i| Point::new(1.0 - i as f32, 0.5 * i as f32))
+        .collect::<Vec<_>>();
+
+    let mut transformed = points.clone();
+    transform_points(&motors, &mut transformed);
+    for ((motor, point), transformed) in motors.iter().zip(&points).zip(&transformed) {
+        assert_eq!(motor.transform(*point), *transformed);
+    }
+
+    let mut composed = motors.clone();
+    compose_motors(&motors, &mut composed);
+    for (motor, composed) in motors.iter().zip(&composed) {
+        assert_eq!(*motor * *motor, *composed);
+    }
+
+    let batch = Point::<F32x8>::splat(Point::new(2.0, 3.0));
+    assert_eq!(batch.lane(5), Point::new(2.0, 3.0));
+}
+
+#[test]
+fn text() {
+    let a = MultiVector {
+        s: 2.0,
+        e0: 3.0,
+        e1: 0.0,
+        e2: -7.0,
+        e01: 11.0,
+        e02: 0.0,
+        e12: -1.5,
+        e012: 0.25,
+    };
+    assert_eq!(a.to_string(), "2 + 3e0 - 7e2 + 11e01 - 1.5e12 + 0.25e012");
+    assert_eq!(a.to_string().parse(), Ok(a));
+    assert_eq!(
+        format!("{:.1}", Point::new(1.0, -2.0)),
+        "-2.0e01 - 1.0e02 + 1.0e12"
+    );
+    assert_eq!((a - a).to_string(), "0");
+
+    let point: Point = "e12 - 3e02 + 2e01 - 1e02".parse().unwrap();
+    assert_eq!(point, Point::new(4.0, 2.0));
+    let point: Point<Fixed> = "-0.5e02 + e12 + 0e0".parse().unwrap();
+    assert_eq!(point.x(), Fixed::HALF);
+
+    assert_eq!(
+        "1 + 2e0".parse::<Point>(),
+        Err(ParseMultiVectorError::InvalidCoefficient {
+            blade: "",
+            coefficient: "1".to_string()
+        })
+    );
+    assert_eq!(
+        "1e3".parse::<MultiVector>(),
+        Err(ParseMultiVectorError::UnknownBlade("e3".to_string()))
+    );
+    assert_eq!(
+        "1 +".parse::<MultiVector>(),
+        Err(ParseMultiVectorError::MissingTerm)
+    );
+    assert_eq!(
+        " ".parse::<MultiVector>(),
+        Err(ParseMultiVectorError::Empty)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    let point = Point::new(1.5, -2.0);
+    let json = serde_json::to_string(&point).unwrap();
+    assert_eq!(json, r#"{"e01":-2.0,"e02":-1.5,"e12":1.0}"#);
+    assert_eq!(serde_json::from_str::<Point>(&json).unwrap(), point);
+    // zero components can be left out, but the others can't
+    assert!(serde_json::from_str::<Point>(r#"{"e01":-2.0,"e12":1.0}"#).is_err());
+    assert!(
+        serde_json::from_str::<Point>(r#"{"e0":1.0,"e01":-2.0,"e02":-1.5,"e12":1.0}"#).is_err()
+    );
+
+    let motor: Motor<Fixed> = Motor::translation(Fixed::ONE, Fixed::ZERO);
+    let json = serde_json::to_string(&motor).unwrap();
+    assert_eq!(serde_json::from_str::<Motor<Fixed>>(&json).unwrap(), motor);
+}
+
+/// Generates random values for the algebraic law tests, [`Zero`] components stay zero so every
+/// typed alias can be generated
+#[cfg(test)]
+trait Random {
+    fn random(rng: &mut fastrand::Rng) -> Self;
+}
+
+#[cfg(test)]
+impl Random for Zero {
+    fn random(_: &mut fastrand::Rng) -> Self {
+        Zero
+    }
+}
+
+#[cfg(test)]
+impl Random for f32 {
+    fn random(rng: &mut fastrand::Rng) -> Self {
+        rng.f32() * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+impl Random for f64 {
+    fn random(rng: &mut fastrand::Rng) -> Self {
+        rng.f64() * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+impl<S, E0, E1, E2, E01, E02, E12, E012> Random
+    for GenericMultiVector<S, E0, E1, E2, E01, E02, E12, E012>
+where
+    S: Value + Random,
+    E0: Value + Random,
+    E1: Value + Random,
+    E2: Value + Random,
+    E01: Value + Random,
+    E02: Value + Random,
+    E12: Value + Random,
+    E012: Value + Random,
+{
+    fn random(rng: &mut fastrand::Rng) -> Self {
+        Self {
+            s: S::random(rng),
+            e0: E0::random(rng),
+            e1: E1::random(rng),
+            e2: E2::random(rng),
+            e01: E01::random(rng),
+            e02: E02::random(rng),
+            e12: E12::random(rng),
+            e012: E012::random(rng),
+        }
+    }
+}
+
+#[test]
+fn approx_eq() {
+    assert_eq!(1.0f32.ulps(1.0 + f32::EPSILON), 1);
+    assert_eq!(0.0f64.ulps(-0.0), 0);
+    assert_eq!((-f32::from_bits(1)).ulps(f32::from_bits(1)), 2);
+
+    let a: Point = Point::new(1.0, 2.0);
+    let b: Point = Point::new(1.0 + 1e-6, 2.0);
+    assert_ne!(a, b);
+    assert!(a.approx_eq(b, 1e-5));
+    assert!(!a.approx_eq(b, 1e-7));
+    assert!(a.approx_eq_relative(b, 1e-5));
+    assert!(!a.approx_eq_ulps(b, 1));
+    assert!(a.approx_eq_ulps(Point::new(f32::from_bits(1.0f32.to_bits() + 1), 2.0), 1));
+
+    // the sandwich has zero scalar and vector parts, but they aren't `Zero` in the type
+    let motor: Motor = Motor::translation(1.0, 0.0);
+    let moved = motor * a * motor.reverse();
+    assert!(moved.approx_eq(Point::new(2.0, 2.0), 1e-6));
+    assert!(!moved.approx_eq(a, 1e-6));
+}
+
+#[test]
+fn algebraic_laws() {
+    let mut rng = fastrand::Rng::with_seed(7);
+    for _ in 0..100 {
+        let [a, b, c] = [(); 3].map(|_| MultiVector::<f64>::random(&mut rng));
+        assert!(((a * b) * c).approx_eq(a * (b * c), 1e-12));
+        assert!((a * (b + c)).approx_eq(a * b + a * c, 1e-12));
+        assert!(((a + b) * c).approx_eq(a * c + b * c, 1e-12));
+        assert!(((a ^ b) ^ c).approx_eq(a ^ (b ^ c), 1e-12));
+        assert!(((a & b) & c).approx_eq(a & (b & c), 1e-12));
+
+        // e0 squares to zero, so only the other vectors contribute to the square of a vector
+        let v = Vector::<f64>::random(&mut rng);
+        assert_eq!((v * v).s, v.e1 * v.e1 + v.e2 * v.e2);
+
+        // the same laws with the typed aliases, where the products mix `Zero` and numbers
+        let point: Point = Random::random(&mut rng);
+        let line: Line = Random::random(&mut rng);
+        let motor: Motor = Random::random(&mut rng);
+        assert!(((motor * point) * line).approx_eq(motor * (point * line), 1e-4f32));
+        assert!(((line * motor) * motor).approx_eq(line * (motor * motor), 1e-4f32));
+        assert!((motor * (point + line)).approx_eq(motor * point + motor * line, 1e-4f32));
+        assert!(((line ^ line) ^ point).approx_eq(line ^ (line ^ point), 1e-4f32));
+    }
+
+    let e0 = GenericMultiVector {
+        s: Zero,
+        e0: 1.0f32,
+        e1: Zero,
+        e2: Zero,
+        e01: Zero,
+        e02: Zero,
+        e12: Zero,
+        e012: Zero,
+    };
+    // every component of the product is `Zero` in the type
+    let _: GenericMultiVector<Zero, Zero, Zero, Zero, Zero, Zero, Zero, Zero> = e0 * e0;
+}
+
+#[test]
+fn scalar_ops() {
+    let point: Point = Point::new(1.0, 2.0);
+    assert_eq!(point * 2.0, 2.0 * point);
+    assert_eq!((point * 2.0).e12, 2.0);
+    assert_eq!((point * 2.0 / 2.0), point);
+    let fixed = Point::new(Fixed::ONE, Fixed::HALF) * Fixed::from_int(2);
+    assert_eq!(fixed.e02, -Fixed::from_int(2));
+
+    let mut motor = Motor::translation(1.0, 0.0);
+    motor *= Motor::rotation(0.5);
+    assert_eq!(motor, Motor::translation(1.0, 0.0) * Motor::rotation(0.5));
+    motor *= 2.0;
+    motor /= 2.0;
+    assert!(motor.approx_eq(Motor::from_cartesian(1.0, 0.0, 0.5), 1e-6));
+
+    let mut a = MultiVector {
+        s: 1.0,
+        e0: 2.0,
+        e1: 3.0,
+        e2: 4.0,
+        e01: 5.0,
+        e02: 6.0,
+        e12: 7.0,
+        e012: 8.0,
+    };
+    let b = a;
+    a += b;
+    a -= b * 0.5;
+    assert_eq!(a, b * 1.5);
+    a *= b;
+    assert_eq!(a, b * 1.5 * b);
+
+    // the typed forms only take values that keep their type
+    let mut line: Line = Line::new(1.0, 0.0, 0.0);
+    line += Line::new(0.0, 1.0, 2.0);
+    assert_eq!(line, Line::new(1.0, 1.0, 2.0));
+
+    assert_eq!(b.grade0().s, 1.0);
+    let vector: Vector = b.grade1();
+    assert_eq!((vector.e0, vector.e1, vector.e2), (2.0, 3.0, 4.0));
+    let bivector: BiVector = b.grade2();
+    assert_eq!((bivector.e01, bivector.e02, bivector.e12), (5.0, 6.0, 7.0));
+    assert_eq!(b.grade3().e012, 8.0);
+    assert_eq!(b.grade0() + vector + bivector + b.grade3(), b);
+    // projecting a typed value keeps its `Zero` components
+    let _: GenericMultiVector<Zero, Zero, Zero, Zero, Zero, Zero, Zero, Zero> = point.grade1();
+}
@@ -0,0 +1,1182 @@
+#[cfg(feature = "serde")]
+use crate::multivector::is_zero;
+use crate::multivector::{Number, Real, Value, Zero};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Mul, Neg, Shl, Shr, Sub};
+
+/// A multivector of the 3D projective geometric algebra R(3,0,1)
+///
+/// This works just like the 2D [`crate::multivector::GenericMultiVector`], every component is
+/// either [`Zero`] or a number so the types keep track of which components can be non-zero
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GenericMultiVector<
+    S,
+    E0,
+    E1,
+    E2,
+    E3,
+    E01,
+    E02,
+    E03,
+    E12,
+    E13,
+    E23,
+    E012,
+    E013,
+    E023,
+    E123,
+    E0123,
+> where
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E3: Value,
+    E01: Value,
+    E02: Value,
+    E03: Value,
+    E12: Value,
+    E13: Value,
+    E23: Value,
+    E012: Value,
+    E013: Value,
+    E023: Value,
+    E123: Value,
+    E0123: Value,
+{
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub s: S,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e0: E0,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e1: E1,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e2: E2,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e3: E3,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e01: E01,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e02: E02,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e03: E03,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e12: E12,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e13: E13,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e23: E23,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e012: E012,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e013: E013,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e023: E023,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e123: E123,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "is_zero"))]
+    pub e0123: E0123,
+}
+
+pub type MultiVector<T = f32> = GenericMultiVector<T, T, T, T, T, T, T, T, T, T, T, T, T, T, T, T>;
+pub type Scalar<T = f32> = GenericMultiVector<
+    T,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+>;
+pub type Vector<T = f32> = GenericMultiVector<
+    Zero,
+    T,
+    T,
+    T,
+    T,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+>;
+pub type Plane<T = f32> = Vector<T>;
+pub type BiVector<T = f32> = GenericMultiVector<
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    T,
+    T,
+    T,
+    T,
+    T,
+    T,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+>;
+pub type Line<T = f32> = BiVector<T>;
+pub type TriVector<T = f32> = GenericMultiVector<
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    T,
+    T,
+    T,
+    T,
+    Zero,
+>;
+pub type Point<T = f32> = TriVector<T>;
+pub type PseudoScalar<T = f32> = GenericMultiVector<
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    Zero,
+    T,
+>;
+pub type Motor<T = f32> =
+    GenericMultiVector<T, Zero, Zero, Zero, Zero, T, T, T, T, T, T, Zero, Zero, Zero, Zero, T>;
+
+type NO<A> = <A as Neg>::Output;
+
+impl<S, E0, E1, E2, E3, E01, E02, E03, E12, E13, E23, E012, E013, E023, E123, E0123> Neg
+    for GenericMultiVector<
+        S,
+        E0,
+        E1,
+        E2,
+        E3,
+        E01,
+        E02,
+        E03,
+        E12,
+        E13,
+        E23,
+        E012,
+        E013,
+        E023,
+        E123,
+        E0123,
+    >
+where
+    S: Value + Neg,
+    E0: Value + Neg,
+    E1: Value + Neg,
+    E2: Value + Neg,
+    E3: Value + Neg,
+    E01: Value + Neg,
+    E02: Value + Neg,
+    E03: Value + Neg,
+    E12: Value + Neg,
+    E13: Value + Neg,
+    E23: Value + Neg,
+    E012: Value + Neg,
+    E013: Value + Neg,
+    E023: Value + Neg,
+    E123: Value + Neg,
+    E0123: Value + Neg,
+    NO<S>: Value,
+    NO<E0>: Value,
+    NO<E1>: Value,
+    NO<E2>: Value,
+    NO<E3>: Value,
+    NO<E01>: Value,
+    NO<E02>: Value,
+    NO<E03>: Value,
+    NO<E12>: Value,
+    NO<E13>: Value,
+    NO<E23>: Value,
+    NO<E012>: Value,
+    NO<E013>: Value,
+    NO<E023>: Value,
+    NO<E123>: Value,
+    NO<E0123>: Value,
+{
+    type Output = GenericMultiVector<
+        NO<S>,
+        NO<E0>,
+        NO<E1>,
+        NO<E2>,
+        NO<E3>,
+        NO<E01>,
+        NO<E02>,
+        NO<E03>,
+        NO<E12>,
+        NO<E13>,
+        NO<E23>,
+        NO<E012>,
+        NO<E013>,
+        NO<E023>,
+        NO<E123>,
+        NO<E0123>,
+    >;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::Output {
+            s: -self.s,
+            e0: -self.e0,
+            e1: -self.e1,
+            e2: -self.e2,
+            e3: -self.e3,
+            e01: -self.e01,
+            e02: -self.e02,
+            e03: -self.e03,
+            e12: -self.e12,
+            e13: -self.e13,
+            e23: -self.e23,
+            e012: -self.e012,
+            e013: -self.e013,
+            e023: -self.e023,
+            e123: -self.e123,
+            e0123: -self.e0123,
+        }
+    }
+}
+
+impl<S, E0, E1, E2, E3, E01, E02, E03, E12, E13, E23, E012, E013, E023, E123, E0123>
+    GenericMultiVector<
+        S,
+        E0,
+        E1,
+        E2,
+        E3,
+        E01,
+        E02,
+        E03,
+        E12,
+        E13,
+        E23,
+        E012,
+        E013,
+        E023,
+        E123,
+        E0123,
+    >
+where
+    S: Value,
+    E0: Value,
+    E1: Value,
+    E2: Value,
+    E3: Value,
+    E01: Value,
+    E02: Value,
+    E03: Value,
+    E12: Value,
+    E13: Value,
+    E23: Value,
+    E012: Value,
+    E013: Value,
+    E023: Value,
+    E123: Value,
+    E0123: Value,
+{
+    /// Reverses the order of the basis vectors in every blade, negating grades 2 and 3
+    #[inline]
+    pub fn reverse(
+        self,
+    ) -> GenericMultiVector<
+        S,
+        E0,
+        E1,
+        E2,
+        E3,
+        NO<E01>,
+        NO<E02>,
+        NO<E03>,
+        NO<E12>,
+        NO<E13>,
+        NO<E23>,
+        NO<E012>,
+        NO<E013>,
+        NO<E023>,
+        NO<E123>,
+        E0123,
+    >
+    where
+        E01: Neg,
+        E02: Neg,
+        E03: Neg,
+        E12: Neg,
+        E13: Neg,
+        E23: Neg,
+        E012: Neg,
+        E013: Neg,
+        E023: Neg,
+        E123: Neg,
+        NO<E01>: Value,
+        NO<E02>: Value,
+        NO<E03>: Value,
+        NO<E12>: Value,
+        NO<E13>: Value,
+        NO<E23>: Value,
+        NO<E012>: Value,
+        NO<E013>: Value,
+        NO<E023>: Value,
+        NO<E123>: Value,
+    {
+        GenericMultiVector {
+            s: self.s,
+            e0: self.e0,
+            e1: self.e1,
+            e2: self.e2,
+            e3: self.e3,
+            e01: -self.e01,
+            e02: -self.e02,
+            e03: -self.e03,
+            e12: -self.e12,
+            e13: -self.e13,
+            e23: -self.e23,
+            e012: -self.e012,
+            e013: -self.e013,
+            e023: -self.e023,
+            e123: -self.e123,
+            e0123: self.e0123,
+        }
+    }
+}
+
+type AO<A, B> = <A as Add<B>>::Output;
+
+impl<
+        LS,
+        LE0,
+        LE1,
+        LE2,
+        LE3,
+        LE01,
+        LE02,
+        LE03,
+        LE12,
+        LE13,
+        LE23,
+        LE012,
+        LE013,
+        LE023,
+        LE123,
+        LE0123,
+        RS,
+        RE0,
+        RE1,
+        RE2,
+        RE3,
+        RE01,
+        RE02,
+        RE03,
+        RE12,
+        RE13,
+        RE23,
+        RE012,
+        RE013,
+        RE023,
+        RE123,
+        RE0123,
+    >
+    Add<
+        GenericMultiVector<
+            RS,
+            RE0,
+            RE1,
+            RE2,
+            RE3,
+            RE01,
+            RE02,
+            RE03,
+            RE12,
+            RE13,
+            RE23,
+            RE012,
+            RE013,
+            RE023,
+            RE123,
+            RE0123,
+        >,
+    >
+    for GenericMultiVector<
+        LS,
+        LE0,
+        LE1,
+        LE2,
+        LE3,
+        LE01,
+        LE02,
+        LE03,
+        LE12,
+        LE13,
+        LE23,
+        LE012,
+        LE013,
+        LE023,
+        LE123,
+        LE0123,
+    >
+where
+    LS: Value + Add<RS>,
+    LE0: Value + Add<RE0>,
+    LE1: Value + Add<RE1>,
+    LE2: Value + Add<RE2>,
+    LE3: Value + Add<RE3>,
+    LE01: Value + Add<RE01>,
+    LE02: Value + Add<RE02>,
+    LE03: Value + Add<RE03>,
+    LE12: Value + Add<RE12>,
+    LE13: Value + Add<RE13>,
+    LE23: Value + Add<RE23>,
+    LE012: Value + Add<RE012>,
+    LE013: Value + Add<RE013>,
+    LE023: Value + Add<RE023>,
+    LE123: Value + Add<RE123>,
+    LE0123: Value + Add<RE0123>,
+    RS: Value,
+    RE0: Value,
+    RE1: Value,
+    RE2: Value,
+    RE3: Value,
+    RE01: Value,
+    RE02: Value,
+    RE03: Value,
+    RE12: Value,
+    RE13: Value,
+    RE23: Value,
+    RE012: Value,
+    RE013: Value,
+    RE023: Value,
+    RE123: Value,
+    RE0123: Value,
+    AO<LS, RS>: Value,
+    AO<LE0, RE0>: Value,
+    AO<LE1, RE1>: Value,
+    AO<LE2, RE2>: Value,
+    AO<LE3, RE3>: Value,
+    AO<LE01, RE01>: Value,
+    AO<LE02, RE02>: Value,
+    AO<LE03, RE03>: Value,
+    AO<LE12, RE12>: Value,
+    AO<LE13, RE13>: Value,
+    AO<LE23, RE23>: Value,
+    AO<LE012, RE012>: Value,
+    AO<LE013, RE013>: Value,
+    AO<LE023, RE023>: Value,
+    AO<LE123, RE123>: Value,
+    AO<LE0123, RE0123>: Value,
+{
+    type Output = GenericMultiVector<
+        AO<LS, RS>,
+        AO<LE0, RE0>,
+        AO<LE1, RE1>,
+        AO<LE2, RE2>,
+        AO<LE3, RE3>,
+        AO<LE01, RE01>,
+        AO<LE02, RE02>,
+        AO<LE03, RE03>,
+        AO<LE12, RE12>,
+        AO<LE13, RE13>,
+        AO<LE23, RE23>,
+        AO<LE012, RE012>,
+        AO<LE013, RE013>,
+        AO<LE023, RE023>,
+        AO<LE123, RE123>,
+        AO<LE0123, RE0123>,
+    >;
+
+    #[inline]
+    fn add(
+        self,
+        rhs: GenericMultiVector<
+            RS,
+            RE0,
+            RE1,
+            RE2,
+            RE3,
+            RE01,
+            RE02,
+            RE03,
+            RE12,
+            RE13,
+            RE23,
+            RE012,
+            RE013,
+            RE023,
+            RE123,
+            RE0123,
+        >,
+    ) -> Self::Output {
+        Self::Output {
+            s: self.s + rhs.s,
+            e0: self.e0 + rhs.e0,
+            e1: self.e1 + rhs.e1,
+            e2: self.e2 + rhs.e2,
+            e3: self.e3 + rhs.e3,
+            e01: self.e01 + rhs.e01,
+            e02: self.e02 + rhs.e02,
+            e03: self.e03 + rhs.e03,
+            e12: self.e12 + rhs.e12,
+            e13: self.e13 + rhs.e13,
+            e23: self.e23 + rhs.e23,
+            e012: self.e012 + rhs.e012,
+            e013: self.e013 + rhs.e013,
+            e023: self.e023 + rhs.e023,
+            e123: self.e123 + rhs.e123,
+            e0123: self.e0123 + rhs.e0123,
+        }
+    }
+}
+
+type SO<A, B> = <A as Sub<B>>::Output;
+
+impl<
+        LS,
+        LE0,
+        LE1,
+        LE2,
+        LE3,
+        LE01,
+        LE02,
+        LE03,
+        LE12,
+        LE13,
+        LE23,
+        LE012,
+        LE013,
+        LE023,
+        LE123,
+        LE0123,
+        RS,
+        RE0,
+        RE1,
+        RE2,
+        RE3,
+        RE01,
+        RE02,
+        RE03,
+        RE12,
+        RE13,
+        RE23,
+        RE012,
+        RE013,
+        RE023,
+        RE123,
+        RE0123,
+    >
+    Sub<
+        GenericMultiVector<
+            RS,
+            RE0,
+            RE1,
+            RE2,
+            RE3,
+            RE01,
+            RE02,
+            RE03,
+            RE12,
+            RE13,
+            RE23,
+            RE012,
+            RE013,
+            RE023,
+            RE123,
+            RE0123,
+        >,
+    >
+    for GenericMultiVector<
+        LS,
+        LE0,
+        LE1,
+        LE2,
+        LE3,
+        LE01,
+        LE02,
+        LE03,
+        LE12,
+        LE13,
+        LE23,
+        LE012,
+        LE013,
+        LE023,
+        LE123,
+        LE0123,
+    >
+where
+    LS: Value + Sub<RS>,
+    LE0: Value + Sub<RE0>,
+    LE1: Value + Sub<RE1>,
+    LE2: Value + Sub<RE2>,
+    LE3: Value + Sub<RE3>,
+    LE01: Value + Sub<RE01>,
+    LE02: Value + Sub<RE02>,
+    LE03: Value + Sub<RE03>,
+    LE12: Value + Sub<RE12>,
+    LE13: Value + Sub<RE13>,
+    LE23: Value + Sub<RE23>,
+    LE012: Value + Sub<RE012>,
+    LE013: Value + Sub<RE013>,
+    LE023: Value + Sub<RE023>,
+    LE123: Value + Sub<RE123>,
+    LE0123: Value + Sub<RE0123>,
+    RS: Value,
+    RE0: Value,
+    RE1: Value,
+    RE2: Value,
+    RE3: Value,
+    RE01: Value,
+    RE02: Value,
+    RE03: Value,
+    RE12: Value,
+    RE13: Value,
+    RE23: Value,
+    RE012: Value,
+    RE013: Value,
+    RE023: Value,
+    RE123: Value,
+    RE0123: Value,
+    SO<LS, RS>: Value,
+    SO<LE0, RE0>: Value,
+    SO<LE1, RE1>: Value,
+    SO<LE2, RE2>: Value,
+    SO<LE3, RE3>: Value,
+    SO<LE01, RE01>: Value,
+    SO<LE02, RE02>: Value,
+    SO<LE03, RE03>: Value,
+    SO<LE12, RE12>: Value,
+    SO<LE13, RE13>: Value,
+    SO<LE23, RE23>: Value,
+    SO<LE012, RE012>: Value,
+    SO<LE013, RE013>: Value,
+    SO<LE023, RE023>: Value,
+    SO<LE123, RE123>: Value,
+    SO<LE0123, RE0123>: Value,
+{
+    type Output = GenericMultiVector<
+        SO<LS, RS>,
+        SO<LE0, RE0>,
+        SO<LE1, RE1>,
+        SO<LE2, RE2>,
+        SO<LE3, RE3>,
+        SO<LE01, RE01>,
+        SO<LE02, RE02>,
+        SO<LE03, RE03>,
+        SO<LE12, RE12>,
+        SO<LE13, RE13>,
+        SO<LE23, RE23>,
+        SO<LE012, RE012>,
+        SO<LE013, RE013>,
+        SO<LE023, RE023>,
+        SO<LE123, RE123>,
+        SO<LE0123, RE0123>,
+    >;
+
+    #[inline]
+    fn sub(
+        self,
+        rhs: GenericMultiVector<
+            RS,
+            RE0,
+            RE1,
+            RE2,
+            RE3,
+            RE01,
+            RE02,
+            RE03,
+            RE12,
+            RE13,
+            RE23,
+            RE012,
+            RE013,
+            RE023,
+            RE123,
+            RE0123,
+        >,
+    ) -> Self::Output {
+        Self::Output {
+            s: self.s - rhs.s,
+            e0: self.e0 - rhs.e0,
+            e1: self.e1 - rhs.e1,
+            e2: self.e2 - rhs.e2,
+            e3: self.e3 - rhs.e3,
+            e01: self.e01 - rhs.e01,
+            e02: self.e02 - rhs.e02,
+            e03: self.e03 - rhs.e03,
+            e12: self.e12 - rhs.e12,
+            e13: self.e13 - rhs.e13,
+            e23: self.e23 - rhs.e23,
+            e012: self.e012 - rhs.e012,
+            e013: self.e013 - rhs.e013,
+            e023: self.e023 - rhs.e023,
+            e123: self.e123 - rhs.e123,
+            e0123: self.e0123 - rhs.e0123,
+        }
+    }
+}
+
+type MO<A, B> = <A as Mul<B>>::Output;
+
+// the geometric, outer, regressive and inner products and the contractions, see `build.rs`
+include!(concat!(env!("OUT_DIR"), "/multivector3d_products.rs"));
+
+impl<T: Number> Motor<T> {
+    pub const IDENTITY: Self = Self {
+        s: T::ONE,
+        e0: Zero,
+        e1: Zero,
+        e2: Zero,
+        e3: Zero,
+        e01: T::ZERO,
+        e02: T::ZERO,
+        e03: T::ZERO,
+        e12: T::ZERO,
+        e13: T::ZERO,
+        e23: T::ZERO,
+        e012: Zero,
+        e013: Zero,
+        e023: Zero,
+        e123: Zero,
+        e0123: T::ZERO,
+    };
+
+    /// Moves everything by `x`, `y` and `z`
+    #[inline]
+    pub fn translation(x: T, y: T, z: T) -> Self {
+        Self {
+            s: T::ONE,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e3: Zero,
+            e01: -(T::HALF * x),
+            e02: -(T::HALF * y),
+            e03: -(T::HALF * z),
+            e12: T::ZERO,
+            e13: T::ZERO,
+            e23: T::ZERO,
+            e012: Zero,
+            e013: Zero,
+            e023: Zero,
+            e123: Zero,
+            e0123: T::ZERO,
+        }
+    }
+
+    /// Returns the motor that applies `self` and then `next`
+    #[inline]
+    pub fn then(self, next: Self) -> Self {
+        next * self
+    }
+
+    /// Applies the motor to `value` with the sandwich product `self * value * self.reverse()`
+    #[inline]
+    pub fn transform<V: Sandwich<T>>(self, value: V) -> V {
+        value.sandwich(self)
+    }
+}
+
+impl<T: Real> Motor<T> {
+    /// Rotates everything by `angle` radians around `axis`, counter-clockwise when looking against
+    /// the direction of the axis
+    ///
+    /// `axis` is expected to be normalized, like the join of two points one unit apart
+    #[inline]
+    pub fn rotation(axis: Line<T>, angle: T) -> Self {
+        let (sin, cos) = (T::HALF * angle).sin_cos();
+        Self {
+            s: cos,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e3: Zero,
+            e01: -(axis.e01 * sin),
+            e02: -(axis.e02 * sin),
+            e03: -(axis.e03 * sin),
+            e12: -(axis.e12 * sin),
+            e13: -(axis.e13 * sin),
+            e23: -(axis.e23 * sin),
+            e012: Zero,
+            e013: Zero,
+            e023: Zero,
+            e123: Zero,
+            e0123: T::ZERO,
+        }
+    }
+}
+
+pub trait Sandwich<T: Number> {
+    fn sandwich(self, motor: Motor<T>) -> Self;
+}
+
+impl<T: Number> Sandwich<T> for Point<T> {
+    #[inline]
+    fn sandwich(self, motor: Motor<T>) -> Self {
+        let result = motor * self * motor.reverse();
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e3: Zero,
+            e01: Zero,
+            e02: Zero,
+            e03: Zero,
+            e12: Zero,
+            e13: Zero,
+            e23: Zero,
+            e012: result.e012,
+            e013: result.e013,
+            e023: result.e023,
+            e123: result.e123,
+            e0123: Zero,
+        }
+    }
+}
+
+impl<T: Number> Sandwich<T> for Plane<T> {
+    #[inline]
+    fn sandwich(self, motor: Motor<T>) -> Self {
+        let result = motor * self * motor.reverse();
+        Self {
+            s: Zero,
+            e0: result.e0,
+            e1: result.e1,
+            e2: result.e2,
+            e3: result.e3,
+            e01: Zero,
+            e02: Zero,
+            e03: Zero,
+            e12: Zero,
+            e13: Zero,
+            e23: Zero,
+            e012: Zero,
+            e013: Zero,
+            e023: Zero,
+            e123: Zero,
+            e0123: Zero,
+        }
+    }
+}
+
+impl<T: Number> Sandwich<T> for Line<T> {
+    #[inline]
+    fn sandwich(self, motor: Motor<T>) -> Self {
+        let result = motor * self * motor.reverse();
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e3: Zero,
+            e01: result.e01,
+            e02: result.e02,
+            e03: result.e03,
+            e12: result.e12,
+            e13: result.e13,
+            e23: result.e23,
+            e012: Zero,
+            e013: Zero,
+            e023: Zero,
+            e123: Zero,
+            e0123: Zero,
+        }
+    }
+}
+
+impl<T: Number> Sandwich<T> for Motor<T> {
+    #[inline]
+    fn sandwich(self, motor: Motor<T>) -> Self {
+        motor * self * motor.reverse()
+    }
+}
+
+impl<T: Number> Point<T> {
+    /// The point at `x`, `y`, `z` with a weight of 1
+    #[inline]
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e3: Zero,
+            e01: Zero,
+            e02: Zero,
+            e03: Zero,
+            e12: Zero,
+            e13: Zero,
+            e23: Zero,
+            e012: -z,
+            e013: y,
+            e023: -x,
+            e123: T::ONE,
+            e0123: Zero,
+        }
+    }
+
+    /// The ideal point (point at infinity) in the direction of `x`, `y`, `z`
+    #[inline]
+    pub fn direction(x: T, y: T, z: T) -> Self {
+        Self {
+            s: Zero,
+            e0: Zero,
+            e1: Zero,
+            e2: Zero,
+            e3: Zero,
+            e01: Zero,
+            e02: Zero,
+            e03: Zero,
+            e12: Zero,
+            e13: Zero,
+            e23: Zero,
+            e012: -z,
+            e013: y,
+            e023: -x,
+            e123: T::ZERO,
+            e0123: Zero,
+        }
+    }
+
+    /// This divides by the weight, so it is only meaningful for points that aren't ideal
+    #[inline]
+    pub fn x(self) -> T {
+        -self.e023 / self.e123
+    }
+
+    /// This divides by the weight, so it is only meaningful for points that aren't ideal
+    #[inline]
+    pub fn y(self) -> T {
+        self.e013 / self.e123
+    }
+
+    /// This divides by the weight, so it is only meaningful for points that aren't ideal
+    #[inline]
+    pub fn z(self) -> T {
+        -self.e012 / self.e123
+    }
+}
+
+impl<T: Number> Line<T> {
+    /// The line going from `a` to `b`
+    #[inline]
+    pub fn through(a: Point<T>, b: Point<T>) -> Self {
+        a & b
+    }
+
+    /// The plane containing `self` and `point`
+    #[inline]
+    pub fn join(self, point: Point<T>) -> Plane<T> {
+        self & point
+    }
+}
+
+impl<T: Number> Plane<T> {
+    /// The plane `a * x + b * y + c * z + d = 0`, with the normal `(a, b, c)`
+    #[inline]
+    pub fn new(a: T, b: T, c: T, d: T) -> Self {
+        Self {
+            s: Zero,
+            e0: d,
+            e1: a,
+            e2: b,
+            e3: c,
+            e01: Zero,
+            e02: Zero,
+            e03: Zero,
+            e12: Zero,
+            e13: Zero,
+            e23: Zero,
+            e012: Zero,
+            e013: Zero,
+            e023: Zero,
+            e123: Zero,
+            e0123: Zero,
+        }
+    }
+
+    /// The plane through `a`, `b` and `c`
+    #[inline]
+    pub fn through(a: Point<T>, b: Point<T>, c: Point<T>) -> Self {
+        a & b & c
+    }
+
+    /// The line where `self` and `other` cross, this is an ideal line if they are parallel
+    #[inline]
+    pub fn intersection(self, other: Self) -> Line<T> {
+        self ^ other
+    }
+
+    /// The point where `self` and `line` cross, this is an ideal point if they are parallel
+    #[inline]
+    pub fn meet(self, line: Line<T>) -> Point<T> {
+        self ^ line
+    }
+}
+
+#[test]
+fn motor() {
+    fn assert_point(point: Point, x: f32, y: f32, z: f32) {
+        assert!(
+            (point.x() - x).abs() < 1e-5
+                && (point.y() - y).abs() < 1e-5
+                && (point.z() - z).abs() < 1e-5,
+            "{point:?} != ({x}, {y}, {z})"
+        );
+    }
+
+    let p = Motor::translation(2.0, -1.0, 0.5).transform(Point::new(1.0, 1.0, 1.0));
+    assert_point(p, 3.0, 0.0, 1.5);
+
+    let origin: Point = Point::new(0.0, 0.0, 0.0);
+    let z_axis = Line::through(origin, Point::new(0.0, 0.0, 1.0));
+    let quarter = Motor::rotation(z_axis, std::f32::consts::FRAC_PI_2);
+    assert_point(quarter.transform(Point::new(1.0, 0.0, 3.0)), 0.0, 1.0, 3.0);
+    // the same rotation as the 2D `Motor::rotation`
+    assert!((quarter.e12 + std::f32::consts::FRAC_PI_4.sin()).abs() < 1e-6);
+
+    let x_axis = Line::through(origin, Point::new(1.0, 0.0, 0.0));
+    let quarter = Motor::rotation(x_axis, std::f32::consts::FRAC_PI_2);
+    assert_point(quarter.transform(Point::new(0.0, 1.0, 0.0)), 0.0, 0.0, 1.0);
+
+    // rotating around a line that doesn't go through the origin
+    let axis = Line::through(Point::new(1.0, 1.0, 0.0), Point::new(1.0, 1.0, 1.0));
+    let half = Motor::rotation(axis, std::f32::consts::PI);
+    assert_point(half.transform(Point::new(2.0, 1.0, 5.0)), 0.0, 1.0, 5.0);
+
+    let composed = quarter.then(Motor::translation(0.0, 0.0, 2.0));
+    assert_point(composed.transform(Point::new(0.0, 1.0, 0.0)), 0.0, 0.0, 3.0);
+    let moved = Motor::translation(1.0, 2.0, 3.0).transform(Motor::IDENTITY);
+    assert_eq!(moved, Motor::IDENTITY);
+
+    let plane = Motor::translation(0.0, 0.0, 1.0).transform(Plane::new(0.0, 0.0, 1.0, 0.0));
+    assert_eq!(plane, Plane::new(0.0, 0.0, 1.0, -1.0));
+}
+
+#[test]
+fn meet_and_join() {
+    let a: Point = Point::new(1.0, 0.0, 0.0);
+    let b: Point = Point::new(0.0, 1.0, 0.0);
+    let c: Point = Point::new(0.0, 0.0, 1.0);
+
+    // the plane x + y + z = 1, up to scale
+    let plane = Plane::through(a, b, c);
+    assert_eq!(plane.e1, plane.e2);
+    assert_eq!(plane.e1, plane.e3);
+    assert_eq!(plane.e0, -plane.e1);
+    assert_eq!(Line::through(a, b).join(c), plane);
+
+    // points on a plane or line join with it to zero
+    assert_eq!((plane & Point::new(0.5, 0.5, 0.0)).s, 0.0);
+    assert_eq!(
+        Line::through(a, b) & Point::new(2.0, -1.0, 0.0),
+        Plane::new(0.0, 0.0, 0.0, 0.0)
+    );
+
+    // the z = 0 plane cuts the line from (0, 0, -1) to (2, 4, 1) at (1, 2, 0)
+    let ground = Plane::new(0.0, 0.0, 1.0, 0.0);
+    let line = Line::through(Point::new(0.0, 0.0, -1.0), Point::new(2.0, 4.0, 1.0));
+    let point = ground.meet(line);
+    assert_eq!((point.x(), point.y(), point.z()), (1.0, 2.0, 0.0));
+
+    // three planes meet in a point
+    let x = Plane::new(1.0, 0.0, 0.0, -3.0);
+    let y = Plane::new(0.0, 1.0, 0.0, 2.0);
+    let z = Plane::new(0.0, 0.0, 1.0, -1.0);
+    let point = x.intersection(y) ^ z;
+    assert_eq!((point.x(), point.y(), point.z()), (3.0, -2.0, 1.0));
+    assert_eq!(x ^ y ^ z, x.meet(y ^ z));
+
+    // parallel planes meet at infinity
+    let ideal = x.intersection(Plane::new(1.0, 0.0, 0.0, 5.0));
+    assert_eq!((ideal.e12, ideal.e13, ideal.e23), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn products_match_reference() {
+    use crate::reference_algebra::ReferenceAlgebra;
+
+    let algebra = ReferenceAlgebra {
+        blades: [
+            0b0000, 0b0001, 0b0010, 0b0100, 0b1000, 0b0011, 0b0101, 0b1001, 0b0110, 0b1010, 0b1100,
+            0b0111, 0b1011, 0b1101, 0b1110, 0b1111,
+        ],
+        metric: &[0.0, 1.0, 1.0, 1.0],
+    };
+
+    let to_array = |m: MultiVector<f64>| {
+        [
+            m.s, m.e0, m.e1, m.e2, m.e3, m.e01, m.e02, m.e03, m.e12, m.e13, m.e23, m.e012, m.e013,
+            m.e023, m.e123, m.e0123,
+        ]
+    };
+    let from_array =
+        |[s, e0, e1, e2, e3, e01, e02, e03, e12, e13, e23, e012, e013, e023, e123, e0123]: [f64;
+            16]| MultiVector {
+            s,
+            e0,
+            e1,
+            e2,
+            e3,
+            e01,
+            e02,
+            e03,
+            e12,
+            e13,
+            e23,
+            e012,
+            e013,
+            e023,
+            e123,
+            e0123,
+        };
+
+    let values = [
+        [
+            2.0, 3.0, 5.0, 7.0, 11.0, 13.0, 17.0, 19.0, 23.0, 29.0, 31.0, 37.0, 41.0, 43.0, 47.0,
+            53.0,
+        ],
+        [
+            59.0, 61.0, 67.0, 71.0, 73.0, 79.0, 83.0, 89.0, 97.0, 101.0, 103.0, 107.0, 109.0,
+            113.0, 127.0, 131.0,
+        ],
+        [
+            -1.0, 0.0, 4.0, -6.0, 2.0, 0.0, -3.0, 8.0, 1.0, 0.0, -2.0, 5.0, 0.0, 3.0, -4.0, 1.0,
+        ],
+        [
+            0.0, -5.0, 1.0, 1.0, -7.0, 9.0, 0.0, -2.0, 0.0, 6.0, 1.0, 0.0, -1.0, 2.0, 0.0, -3.0,
+        ],
+    ];
+    algebra.assert_products_match(&values, to_array, from_array);
+}
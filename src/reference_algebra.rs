@@ -0,0 +1,126 @@
+//! An independent implementation of projective geometric algebra, with blades stored as bitmasks of
+//! their basis vectors, to check the products of both multivector modules against
+
+use std::ops::{BitAnd, BitOr, BitXor, Mul, Shl, Shr};
+
+/// An algebra with as many basis vectors as `metric` has entries, where `e0` is bit 0 of a blade
+pub(crate) struct ReferenceAlgebra<const N: usize> {
+    /// Every blade, in the order of the fields of the multivector
+    pub(crate) blades: [u32; N],
+    /// What each basis vector squares to
+    pub(crate) metric: &'static [f64],
+}
+
+/// The sign from moving every basis vector of `b` past the basis vectors of `a` after it
+fn reorder_sign(a: u32, b: u32) -> f64 {
+    let mut a = a >> 1;
+    let mut swaps = 0;
+    while a != 0 {
+        swaps += (a & b).count_ones();
+        a >>= 1;
+    }
+    if swaps % 2 == 0 {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+impl<const N: usize> ReferenceAlgebra<N> {
+    fn pseudoscalar(&self) -> u32 {
+        (1 << self.metric.len()) - 1
+    }
+
+    fn geometric(&self, a: u32, b: u32) -> Option<(f64, u32)> {
+        let mut sign = reorder_sign(a, b);
+        for (i, square) in self.metric.iter().enumerate() {
+            if a & b & (1 << i) != 0 {
+                sign *= square;
+            }
+        }
+        Some((sign, a ^ b))
+    }
+
+    fn outer(&self, a: u32, b: u32) -> Option<(f64, u32)> {
+        (a & b == 0).then(|| (reorder_sign(a, b), a | b))
+    }
+
+    fn left_contraction(&self, a: u32, b: u32) -> Option<(f64, u32)> {
+        (a & !b == 0).then(|| self.geometric(a, b).unwrap())
+    }
+
+    fn right_contraction(&self, a: u32, b: u32) -> Option<(f64, u32)> {
+        (b & !a == 0).then(|| self.geometric(a, b).unwrap())
+    }
+
+    fn inner(&self, a: u32, b: u32) -> Option<(f64, u32)> {
+        self.left_contraction(a, b)
+            .or_else(|| self.right_contraction(a, b))
+    }
+
+    /// a v b = J^-1(J(a) ^ J(b)), where J is the right complement
+    fn regressive(&self, a: u32, b: u32) -> Option<(f64, u32)> {
+        let pseudoscalar = self.pseudoscalar();
+        let complement = |blade: u32| {
+            (
+                reorder_sign(blade, pseudoscalar ^ blade),
+                pseudoscalar ^ blade,
+            )
+        };
+        let (a_sign, a) = complement(a);
+        let (b_sign, b) = complement(b);
+        let (sign, blade) = self.outer(a, b)?;
+        let inverse_sign = reorder_sign(pseudoscalar ^ blade, blade);
+        Some((a_sign * b_sign * sign * inverse_sign, pseudoscalar ^ blade))
+    }
+
+    /// Extends a product of blades to multivectors by linearity
+    fn product(
+        &self,
+        a: [f64; N],
+        b: [f64; N],
+        product: impl Fn(&Self, u32, u32) -> Option<(f64, u32)>,
+    ) -> [f64; N] {
+        let mut result = [0.0; N];
+        for (i, &left) in self.blades.iter().enumerate() {
+            for (j, &right) in self.blades.iter().enumerate() {
+                if let Some((sign, blade)) = product(self, left, right) {
+                    let k = self.blades.iter().position(|&b| b == blade).unwrap();
+                    result[k] += sign * a[i] * b[j];
+                }
+            }
+        }
+        result
+    }
+
+    /// Checks every product operator of `M` against this algebra for every pair of `values`
+    pub(crate) fn assert_products_match<M>(
+        &self,
+        values: &[[f64; N]],
+        to_array: impl Fn(M) -> [f64; N],
+        from_array: impl Fn([f64; N]) -> M,
+    ) where
+        M: Copy
+            + Mul<Output = M>
+            + BitXor<Output = M>
+            + BitAnd<Output = M>
+            + BitOr<Output = M>
+            + Shl<Output = M>
+            + Shr<Output = M>,
+    {
+        for &a in values {
+            for &b in values {
+                let (x, y) = (from_array(a), from_array(b));
+                assert_eq!(to_array(x * y), self.product(a, b, Self::geometric));
+                assert_eq!(to_array(x ^ y), self.product(a, b, Self::outer));
+                assert_eq!(to_array(x & y), self.product(a, b, Self::regressive));
+                assert_eq!(to_array(x | y), self.product(a, b, Self::inner));
+                assert_eq!(to_array(x << y), self.product(a, b, Self::left_contraction));
+                assert_eq!(
+                    to_array(x >> y),
+                    self.product(a, b, Self::right_contraction)
+                );
+            }
+        }
+    }
+}
@@ -1,65 +1,285 @@
 use crate::{
+    time::Time,
     window::{InitWindowInternals, WindowSize},
-    Circle, Material, Quad, {Camera, GlobalTransform},
+    BlendMode, Circle, Layer, Material, Outline, PreviousGlobalTransform, Quad,
+    {Camera, GlobalTransform},
 };
 use bevy::{
-    ecs::schedule::ScheduleLabel,
+    ecs::{schedule::ScheduleLabel, system::SystemParam},
+    math::{Affine2, Vec2},
     prelude::{
-        resource_changed, App, DetectChanges, IntoSystemConfigs, Plugin, Query, Ref, Res, ResMut,
-        Resource,
+        resource_changed, App, DetectChanges, IntoSystemConfigs, Plugin, Query, Ref,
+        RemovedComponents, Res, ResMut, Resource,
     },
 };
-use encase::{DynamicStorageBuffer, ShaderSize, ShaderType, UniformBuffer};
+use encase::{internal::WriteInto, DynamicStorageBuffer, ShaderSize, ShaderType, UniformBuffer};
+use enum_map::{enum_map, EnumMap};
+use gpu::{GpuCamera, GpuCircle, GpuQuad, GpuTransform};
+use std::{marker::PhantomData, ops::Range};
 use wgpu::include_wgsl;
 
 #[derive(ScheduleLabel, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct RenderSchedule;
 
-#[derive(ShaderType)]
-struct GpuCamera {
-    x: f32,
-    y: f32,
-    aspect: f32,
-    vertical_height: f32,
+// the `ShaderType` derive emits a `check` fn per field that is never called, next to the type
+// rather than inside it, so the GPU types get a module of their own to allow that in
+#[allow(dead_code)]
+mod gpu {
+    use encase::ShaderType;
+
+    #[derive(ShaderType)]
+    pub(super) struct GpuCamera {
+        pub(super) world_to_clip: GpuTransform,
+    }
+
+    /// The columns of the affine transform of the global transform, so that a point `(x, y)` in the
+    /// shape's own space ends up at `translation + x * x_axis + y * y_axis`
+    #[derive(ShaderType)]
+    pub(super) struct GpuTransform {
+        pub(super) translation_x: f32,
+        pub(super) translation_y: f32,
+        pub(super) x_axis_x: f32,
+        pub(super) x_axis_y: f32,
+        pub(super) y_axis_x: f32,
+        pub(super) y_axis_y: f32,
+    }
+
+    #[derive(ShaderType)]
+    pub(super) struct GpuQuad {
+        pub(super) transform: GpuTransform,
+        pub(super) depth: f32,
+        pub(super) width: f32,
+        pub(super) height: f32,
+        pub(super) red: f32,
+        pub(super) green: f32,
+        pub(super) blue: f32,
+        pub(super) alpha: f32,
+        /// Zero without an [`Outline`](crate::Outline)
+        pub(super) outline_width: f32,
+        pub(super) outline_red: f32,
+        pub(super) outline_green: f32,
+        pub(super) outline_blue: f32,
+        pub(super) outline_alpha: f32,
+    }
+
+    #[derive(ShaderType)]
+    pub(super) struct GpuCircle {
+        pub(super) transform: GpuTransform,
+        pub(super) depth: f32,
+        pub(super) radius: f32,
+        pub(super) red: f32,
+        pub(super) green: f32,
+        pub(super) blue: f32,
+        pub(super) alpha: f32,
+        /// Zero without an [`Outline`](crate::Outline)
+        pub(super) outline_width: f32,
+        pub(super) outline_red: f32,
+        pub(super) outline_green: f32,
+        pub(super) outline_blue: f32,
+        pub(super) outline_alpha: f32,
+    }
+}
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+/// Maps every layer to its own depth, from the back of the depth buffer at `i16::MIN` towards the
+/// front, so that drawing with [`wgpu::CompareFunction::LessEqual`] stacks the layers
+fn layer_depth(Layer(layer): Layer) -> f32 {
+    (i16::MAX as f32 + 1.0 - layer as f32) / 65536.0
 }
 
-#[derive(ShaderType)]
-struct GpuQuad {
-    x: f32,
-    y: f32,
-    width: f32,
-    height: f32,
-    red: f32,
-    green: f32,
-    blue: f32,
+fn create_depth_texture_view(
+    device: &wgpu::Device,
+    surface_configuration: &wgpu::SurfaceConfiguration,
+) -> wgpu::TextureView {
+    device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Depth Texture"),
+            size: wgpu::Extent3d {
+                width: surface_configuration.width,
+                height: surface_configuration.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: DEPTH_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
 }
 
-#[derive(ShaderType)]
-struct GpuCircle {
-    x: f32,
-    y: f32,
-    radius: f32,
-    red: f32,
-    green: f32,
-    blue: f32,
+/// Opaque shapes replace what is behind them and write their depth, translucent ones blend the
+/// premultiplied color from the shader over what is behind them and leave the depth alone
+fn create_shape_render_pipeline(
+    device: &wgpu::Device,
+    label: &str,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    blend_mode: Option<BlendMode>,
+) -> wgpu::RenderPipeline {
+    let blend = blend_mode.map(|blend_mode| wgpu::BlendState {
+        color: match blend_mode {
+            BlendMode::Alpha => wgpu::BlendComponent::OVER,
+            BlendMode::Additive => wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::One,
+                dst_factor: wgpu::BlendFactor::One,
+                operation: wgpu::BlendOperation::Add,
+            },
+            BlendMode::Multiply => wgpu::BlendComponent {
+                src_factor: wgpu::BlendFactor::Dst,
+                dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                operation: wgpu::BlendOperation::Add,
+            },
+        },
+        alpha: wgpu::BlendComponent::OVER,
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: "vertex",
+            buffers: &[],
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleStrip,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Cw,
+            cull_mode: None,
+            unclipped_depth: false,
+            polygon_mode: wgpu::PolygonMode::Fill,
+            conservative: false,
+        },
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: blend_mode.is_none(),
+            depth_compare: wgpu::CompareFunction::LessEqual,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: wgpu::MultisampleState {
+            count: 1,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: "pixel",
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        multiview: None,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Quad,
+    Circle,
+}
+
+/// Consecutive translucent instances of one shape that can be drawn with a single draw call
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TranslucentBatch {
+    shape: Shape,
+    blend_mode: BlendMode,
+    instances: Range<u32>,
+}
+
+/// Interleaves the translucent quads and circles, each sorted back to front and starting at the
+/// given instance, into as few draws as possible while keeping them back to front
+///
+/// Quads go first within a layer, the same as for opaque shapes
+fn translucent_batches(
+    quads: &[(Layer, BlendMode)],
+    first_quad: u32,
+    circles: &[(Layer, BlendMode)],
+    first_circle: u32,
+) -> Vec<TranslucentBatch> {
+    let mut batches: Vec<TranslucentBatch> = vec![];
+    let (mut quads, mut circles) = (quads.iter().peekable(), circles.iter().peekable());
+    let (mut next_quad, mut next_circle) = (first_quad, first_circle);
+    loop {
+        let quad_first = match (quads.peek(), circles.peek()) {
+            (Some((quad_layer, _)), Some((circle_layer, _))) => quad_layer <= circle_layer,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
+        };
+        let (shape, &(_, blend_mode), instance) = if quad_first {
+            next_quad += 1;
+            (Shape::Quad, quads.next().unwrap(), next_quad - 1)
+        } else {
+            next_circle += 1;
+            (Shape::Circle, circles.next().unwrap(), next_circle - 1)
+        };
+        match batches.last_mut() {
+            Some(batch)
+                if batch.shape == shape
+                    && batch.blend_mode == blend_mode
+                    && batch.instances.end == instance =>
+            {
+                batch.instances.end += 1;
+            }
+            _ => batches.push(TranslucentBatch {
+                shape,
+                blend_mode,
+                instances: instance..instance + 1,
+            }),
+        }
+    }
+    batches
+}
+
+/// Undoes the camera's own transform, so that its rotation and scale turn the view as well, and
+/// then fits `vertical_height` world units into the height of the window
+fn world_to_clip(camera_transform: &GlobalTransform, camera: &Camera, aspect: f32) -> Affine2 {
+    Affine2::from_scale(Vec2::new(aspect.recip(), 1.0) * 2.0 / camera.vertical_height)
+        * camera_transform.affine().inverse()
+}
+
+impl From<&GlobalTransform> for GpuTransform {
+    fn from(global_transform: &GlobalTransform) -> Self {
+        Self::from(global_transform.affine())
+    }
+}
+
+impl From<&Affine2> for GpuTransform {
+    fn from(affine: &Affine2) -> Self {
+        Self {
+            translation_x: affine.translation.x,
+            translation_y: affine.translation.y,
+            x_axis_x: affine.matrix2.x_axis.x,
+            x_axis_y: affine.matrix2.x_axis.y,
+            y_axis_x: affine.matrix2.y_axis.x,
+            y_axis_y: affine.matrix2.y_axis.y,
+        }
+    }
 }
 
 #[derive(Resource)]
 struct Renderer {
-    circle_render_pipeline: wgpu::RenderPipeline,
+    /// Circles are always blended, as their edges are anti-aliased
+    circle_render_pipelines: EnumMap<BlendMode, wgpu::RenderPipeline>,
     circle_buffer: wgpu::Buffer,
     circle_buffer_size: wgpu::BufferAddress,
-    circle_count: u32,
     circle_bind_group_layout: wgpu::BindGroupLayout,
     circle_bind_group: wgpu::BindGroup,
     quad_render_pipeline: wgpu::RenderPipeline,
+    translucent_quad_render_pipelines: EnumMap<BlendMode, wgpu::RenderPipeline>,
     quad_buffer: wgpu::Buffer,
     quad_buffer_size: wgpu::BufferAddress,
-    quad_count: u32,
     quad_bind_group_layout: wgpu::BindGroupLayout,
     quad_bind_group: wgpu::BindGroup,
     camera_uniform_buffer: wgpu::Buffer,
     camera_bind_group: wgpu::BindGroup,
+    depth_texture_view: wgpu::TextureView,
     queue: wgpu::Queue,
     device: wgpu::Device,
     _adapter: wgpu::Adapter,
@@ -125,6 +345,7 @@ impl Plugin for RendererPlugin {
             view_formats: vec![],
         };
         surface.configure(&device, &surface_configuration);
+        let depth_texture_view = create_depth_texture_view(&device, &surface_configuration);
 
         let camera_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Camera Uniform Buffer"),
@@ -196,36 +417,24 @@ impl Plugin for RendererPlugin {
                 bind_group_layouts: &[&camera_bind_group_layout, &quad_bind_group_layout],
                 push_constant_ranges: &[],
             });
-        let quad_render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Quad Render Pipeline"),
-            layout: Some(&quad_render_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &quad_shader,
-                entry_point: "vertex",
-                buffers: &[],
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleStrip,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Cw,
-                cull_mode: None,
-                unclipped_depth: false,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &quad_shader,
-                entry_point: "pixel",
-                targets: &[Some(surface_configuration.format.into())],
-            }),
-            multiview: None,
-        });
+        let quad_render_pipeline = create_shape_render_pipeline(
+            &device,
+            "Quad Render Pipeline",
+            &quad_render_pipeline_layout,
+            &quad_shader,
+            surface_configuration.format,
+            None,
+        );
+        let translucent_quad_render_pipelines = enum_map! {
+            blend_mode => create_shape_render_pipeline(
+                &device,
+                &format!("Translucent Quad Render Pipeline ({blend_mode:?})"),
+                &quad_render_pipeline_layout,
+                &quad_shader,
+                surface_configuration.format,
+                Some(blend_mode),
+            ),
+        };
 
         let circle_buffer_size = GpuCircle::SHADER_SIZE;
         let circle_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -266,53 +475,32 @@ impl Plugin for RendererPlugin {
                 bind_group_layouts: &[&camera_bind_group_layout, &circle_bind_group_layout],
                 push_constant_ranges: &[],
             });
-        let circle_render_pipeline =
-            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-                label: Some("Circle Render Pipeline"),
-                layout: Some(&circle_render_pipeline_layout),
-                vertex: wgpu::VertexState {
-                    module: &circle_shader,
-                    entry_point: "vertex",
-                    buffers: &[],
-                },
-                primitive: wgpu::PrimitiveState {
-                    topology: wgpu::PrimitiveTopology::TriangleStrip,
-                    strip_index_format: None,
-                    front_face: wgpu::FrontFace::Cw,
-                    cull_mode: None,
-                    unclipped_depth: false,
-                    polygon_mode: wgpu::PolygonMode::Fill,
-                    conservative: false,
-                },
-                depth_stencil: None,
-                multisample: wgpu::MultisampleState {
-                    count: 1,
-                    mask: !0,
-                    alpha_to_coverage_enabled: false,
-                },
-                fragment: Some(wgpu::FragmentState {
-                    module: &circle_shader,
-                    entry_point: "pixel",
-                    targets: &[Some(surface_configuration.format.into())],
-                }),
-                multiview: None,
-            });
+        let circle_render_pipelines = enum_map! {
+            blend_mode => create_shape_render_pipeline(
+                &device,
+                &format!("Circle Render Pipeline ({blend_mode:?})"),
+                &circle_render_pipeline_layout,
+                &circle_shader,
+                surface_configuration.format,
+                Some(blend_mode),
+            ),
+        };
 
         app.insert_resource(Renderer {
-            circle_render_pipeline,
+            circle_render_pipelines,
             circle_buffer,
             circle_buffer_size: circle_buffer_size.get(),
-            circle_count: 0,
             circle_bind_group,
             circle_bind_group_layout,
             quad_render_pipeline,
+            translucent_quad_render_pipelines,
             quad_buffer,
             quad_buffer_size: quad_buffer_size.get(),
-            quad_count: 0,
             quad_bind_group,
             quad_bind_group_layout,
             camera_uniform_buffer,
             camera_bind_group,
+            depth_texture_view,
             queue,
             device,
             _adapter: adapter,
@@ -320,6 +508,8 @@ impl Plugin for RendererPlugin {
             surface,
             _instance: instance,
         })
+        .init_resource::<ShapeInstances<Quad>>()
+        .init_resource::<ShapeInstances<Circle>>()
         .init_schedule(RenderSchedule)
         .add_systems(
             RenderSchedule,
@@ -327,8 +517,8 @@ impl Plugin for RendererPlugin {
                 (
                     on_resize.run_if(resource_changed::<WindowSize>()),
                     update_camera,
-                    update_quads,
-                    update_circles,
+                    (prepare_quads, update_quads).chain(),
+                    (prepare_circles, update_circles).chain(),
                 ),
                 render,
             )
@@ -343,24 +533,80 @@ fn on_resize(mut renderer: ResMut<'_, Renderer>, size: Res<'_, WindowSize>) {
     renderer
         .surface
         .configure(&renderer.device, &renderer.surface_configuration);
+    renderer.depth_texture_view =
+        create_depth_texture_view(&renderer.device, &renderer.surface_configuration);
+}
+
+/// Where to draw an entity this frame, blended over the last fixed step, and whether that can differ
+/// from what was drawn last frame
+fn render_transform(
+    global_transform: &Ref<'_, GlobalTransform>,
+    previous_global_transform: Option<Ref<'_, PreviousGlobalTransform>>,
+    time: &Time,
+) -> (GlobalTransform, bool) {
+    let Some(previous_global_transform) = previous_global_transform else {
+        return (**global_transform, global_transform.is_changed());
+    };
+    let moving = previous_global_transform.0 != global_transform.0;
+    let transform = if moving {
+        previous_global_transform.interpolate(global_transform, time.overstep_fraction())
+    } else {
+        **global_transform
+    };
+    let changed = moving || global_transform.is_changed() || previous_global_transform.is_changed();
+    (transform, changed)
+}
+
+/// Whether a shape can be drawn without blending, which the depth buffer can then sort
+fn is_opaque(material: &Material, outline: Option<&Outline>) -> bool {
+    material.is_opaque() && outline.is_none_or(Outline::is_opaque)
+}
+
+/// The optional components that change how a shape is drawn, which can be removed without the
+/// shape itself changing
+#[derive(SystemParam)]
+struct RemovedShapeComponents<'w, 's> {
+    materials: RemovedComponents<'w, 's, Material>,
+    layers: RemovedComponents<'w, 's, Layer>,
+    outlines: RemovedComponents<'w, 's, Outline>,
+}
+
+impl RemovedShapeComponents<'_, '_> {
+    /// Whether anything was removed since the last time this was called
+    fn any(&mut self) -> bool {
+        let any =
+            !(self.materials.is_empty() && self.layers.is_empty() && self.outlines.is_empty());
+        self.materials.clear();
+        self.layers.clear();
+        self.outlines.clear();
+        any
+    }
 }
 
 fn update_camera(
     renderer: Res<'_, Renderer>,
-    camera: Query<'_, '_, (Ref<'_, GlobalTransform>, Ref<'_, Camera>)>,
+    camera: Query<
+        '_,
+        '_,
+        (
+            Ref<'_, GlobalTransform>,
+            Option<Ref<'_, PreviousGlobalTransform>>,
+            Ref<'_, Camera>,
+        ),
+    >,
     size: Res<'_, WindowSize>,
+    time: Res<'_, Time>,
 ) {
-    let (global_transform, camera) = camera.get_single().unwrap();
-    if !global_transform.is_changed() && !camera.is_changed() && !size.is_changed() {
+    let (global_transform, previous_global_transform, camera) = camera.get_single().unwrap();
+    let (global_transform, transform_changed) =
+        render_transform(&global_transform, previous_global_transform, &time);
+    if !transform_changed && !camera.is_changed() && !size.is_changed() {
         return;
     }
 
-    let transform = global_transform.transform();
+    let aspect = size.width().get() as f32 / size.height().get() as f32;
     let gpu_camera = GpuCamera {
-        x: transform.x,
-        y: transform.y,
-        aspect: size.width().get() as f32 / size.height().get() as f32,
-        vertical_height: camera.vertical_height,
+        world_to_clip: (&world_to_clip(&global_transform, &camera, aspect)).into(),
     };
 
     let mut buffer = UniformBuffer::new([0u8; GpuCamera::SHADER_SIZE.get() as _]);
@@ -372,148 +618,276 @@ fn update_camera(
         .write_buffer(&renderer.camera_uniform_buffer, 0, &buffer);
 }
 
-// TODO: find a way to only upload changed quads
-fn update_quads(
-    mut renderer: ResMut<'_, Renderer>,
+/// The instances of one shape as they were last uploaded to its storage buffer, which only changes
+/// when they have to be uploaded again
+#[derive(Resource)]
+struct ShapeInstances<S> {
+    /// The contents of the storage buffer, the opaque instances first and then the translucent ones
+    /// back to front
+    buffer: Vec<u8>,
+    opaque_count: u32,
+    translucent: Vec<(Layer, BlendMode)>,
+    _shape: PhantomData<S>,
+}
+
+impl<S> Default for ShapeInstances<S> {
+    fn default() -> Self {
+        Self {
+            buffer: vec![],
+            opaque_count: 0,
+            translucent: vec![],
+            _shape: PhantomData,
+        }
+    }
+}
+
+/// Replaces the instances when anything about them changed
+///
+/// The translucent instances are also compared, as the query order, and so their order, can change
+/// without any change tick when an entity moves to another archetype, and the draws have to match
+/// the order that is on the GPU
+fn replace_instances<S: Send + Sync + 'static, T: ShaderType + WriteInto>(
+    instances: &mut ResMut<'_, ShapeInstances<S>>,
+    anything_changed: bool,
+    opaque: &[T],
+    translucent: &[(Layer, BlendMode, T)],
+) {
+    let opaque_count: u32 = opaque.len().try_into().unwrap();
+    let changed = anything_changed
+        || opaque_count != instances.opaque_count
+        || !translucent
+            .iter()
+            .map(|&(layer, blend_mode, _)| (layer, blend_mode))
+            .eq(instances.translucent.iter().copied());
+    if !changed {
+        return;
+    }
+
+    let mut buffer = DynamicStorageBuffer::new(vec![]);
+    for instance in opaque
+        .iter()
+        .chain(translucent.iter().map(|(_, _, instance)| instance))
+    {
+        buffer.write(instance).unwrap();
+    }
+    **instances = ShapeInstances {
+        buffer: buffer.into_inner(),
+        opaque_count,
+        translucent: translucent
+            .iter()
+            .map(|&(layer, blend_mode, _)| (layer, blend_mode))
+            .collect(),
+        _shape: PhantomData,
+    };
+}
+
+fn prepare_quads(
+    mut instances: ResMut<'_, ShapeInstances<Quad>>,
     quads: Query<
         '_,
         '_,
         (
             Ref<'_, GlobalTransform>,
+            Option<Ref<'_, PreviousGlobalTransform>>,
             Ref<'_, Quad>,
             Option<Ref<'_, Material>>,
+            Option<Ref<'_, Layer>>,
+            Option<Ref<'_, Outline>>,
         ),
     >,
+    mut removed: RemovedShapeComponents<'_, '_>,
+    time: Res<'_, Time>,
 ) {
-    let mut anything_changed = false;
-    let mut quad_count = 0usize;
-    let mut buffer = DynamicStorageBuffer::new(vec![]);
-    for (global_transform, quad, material) in &quads {
-        quad_count += 1;
-        anything_changed |= global_transform.is_changed() || quad.is_changed();
-        let transform = global_transform.transform();
-        let (red, green, blue) = material.map_or((1.0, 1.0, 1.0), |material| {
+    let mut anything_changed = removed.any();
+    let mut opaque_quads = vec![];
+    let mut translucent_quads = vec![];
+    for (global_transform, previous_global_transform, quad, material, layer, outline) in &quads {
+        let (global_transform, transform_changed) =
+            render_transform(&global_transform, previous_global_transform, &time);
+        anything_changed |= transform_changed || quad.is_changed();
+        let material = material.map_or(Material::WHITE, |material| {
             anything_changed |= material.is_changed();
-            let Material { red, green, blue } = *material;
-            (red, green, blue)
+            *material
         });
-        buffer
-            .write(&GpuQuad {
-                x: transform.x,
-                y: transform.y,
-                width: quad.width,
-                height: quad.height,
-                red,
-                green,
-                blue,
-            })
-            .unwrap();
+        anything_changed |= layer.as_ref().is_some_and(DetectChanges::is_changed);
+        let layer = layer.as_deref().copied().unwrap_or_default();
+        let outline = outline.map(|outline| {
+            anything_changed |= outline.is_changed();
+            *outline
+        });
+        let Outline {
+            width: outline_width,
+            red: outline_red,
+            green: outline_green,
+            blue: outline_blue,
+            alpha: outline_alpha,
+        } = outline.unwrap_or(Outline::rgba(0.0, 0.0, 0.0, 0.0, 0.0));
+        let gpu_quad = GpuQuad {
+            transform: (&global_transform).into(),
+            depth: layer_depth(layer),
+            width: quad.width,
+            height: quad.height,
+            red: material.red,
+            green: material.green,
+            blue: material.blue,
+            alpha: material.alpha,
+            outline_width,
+            outline_red,
+            outline_green,
+            outline_blue,
+            outline_alpha,
+        };
+        if is_opaque(&material, outline.as_ref()) {
+            opaque_quads.push(gpu_quad);
+        } else {
+            translucent_quads.push((layer, material.blend_mode, gpu_quad));
+        }
     }
+    // back to front, and otherwise in query order
+    translucent_quads.sort_by_key(|&(layer, _, _)| layer);
 
-    let quad_count = quad_count.try_into().unwrap();
-    if anything_changed || quad_count != renderer.quad_count {
-        renderer.quad_count = quad_count;
-        let buffer = buffer.into_inner();
-
-        let required_buffer_size: wgpu::BufferAddress = buffer.len().try_into().unwrap();
-        if required_buffer_size > renderer.quad_buffer_size {
-            renderer.quad_buffer_size = required_buffer_size;
-            renderer.quad_buffer = renderer.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Quad Storage Buffer"),
-                size: renderer.quad_buffer_size,
-                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::STORAGE,
-                mapped_at_creation: false,
-            });
-            renderer.quad_bind_group =
-                renderer
-                    .device
-                    .create_bind_group(&wgpu::BindGroupDescriptor {
-                        label: Some("Quad Bind Group"),
-                        layout: &renderer.quad_bind_group_layout,
-                        entries: &[wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: renderer.quad_buffer.as_entire_binding(),
-                        }],
-                    });
-        }
+    replace_instances(
+        &mut instances,
+        anything_changed,
+        &opaque_quads,
+        &translucent_quads,
+    );
+}
+
+// TODO: find a way to only upload changed quads
+fn update_quads(mut renderer: ResMut<'_, Renderer>, quads: Res<'_, ShapeInstances<Quad>>) {
+    if !quads.is_changed() {
+        return;
+    }
 
-        renderer
-            .queue
-            .write_buffer(&renderer.quad_buffer, 0, &buffer);
+    let required_buffer_size: wgpu::BufferAddress = quads.buffer.len().try_into().unwrap();
+    if required_buffer_size > renderer.quad_buffer_size {
+        renderer.quad_buffer_size = required_buffer_size;
+        renderer.quad_buffer = renderer.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Quad Storage Buffer"),
+            size: renderer.quad_buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        renderer.quad_bind_group = renderer
+            .device
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("Quad Bind Group"),
+                layout: &renderer.quad_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: renderer.quad_buffer.as_entire_binding(),
+                }],
+            });
     }
+
+    renderer
+        .queue
+        .write_buffer(&renderer.quad_buffer, 0, &quads.buffer);
 }
 
-// TODO: find a way to only upload changed circles
-fn update_circles(
-    mut renderer: ResMut<'_, Renderer>,
+fn prepare_circles(
+    mut instances: ResMut<'_, ShapeInstances<Circle>>,
     circles: Query<
         '_,
         '_,
         (
             Ref<'_, GlobalTransform>,
+            Option<Ref<'_, PreviousGlobalTransform>>,
             Ref<'_, Circle>,
             Option<Ref<'_, Material>>,
+            Option<Ref<'_, Layer>>,
+            Option<Ref<'_, Outline>>,
         ),
     >,
+    mut removed: RemovedShapeComponents<'_, '_>,
+    time: Res<'_, Time>,
 ) {
-    let mut anything_changed = false;
-    let mut circle_count = 0usize;
-    let mut buffer = DynamicStorageBuffer::new(vec![]);
-    for (global_transform, circle, material) in &circles {
-        circle_count += 1;
-        anything_changed |= global_transform.is_changed() || circle.is_changed();
-        let transform = global_transform.transform();
-        let (red, green, blue) = material.map_or((1.0, 1.0, 1.0), |material| {
+    let mut anything_changed = removed.any();
+    let mut circles_to_draw = vec![];
+    for (global_transform, previous_global_transform, circle, material, layer, outline) in &circles
+    {
+        let (global_transform, transform_changed) =
+            render_transform(&global_transform, previous_global_transform, &time);
+        anything_changed |= transform_changed || circle.is_changed();
+        let material = material.map_or(Material::WHITE, |material| {
             anything_changed |= material.is_changed();
-            let Material { red, green, blue } = *material;
-            (red, green, blue)
+            *material
+        });
+        anything_changed |= layer.as_ref().is_some_and(DetectChanges::is_changed);
+        let layer = layer.as_deref().copied().unwrap_or_default();
+        let outline = outline.map(|outline| {
+            anything_changed |= outline.is_changed();
+            *outline
         });
-        buffer
-            .write(&GpuCircle {
-                x: transform.x,
-                y: transform.y,
-                radius: circle.radius,
-                red,
-                green,
-                blue,
-            })
-            .unwrap();
+        let Outline {
+            width: outline_width,
+            red: outline_red,
+            green: outline_green,
+            blue: outline_blue,
+            alpha: outline_alpha,
+        } = outline.unwrap_or(Outline::rgba(0.0, 0.0, 0.0, 0.0, 0.0));
+        let gpu_circle = GpuCircle {
+            transform: (&global_transform).into(),
+            depth: layer_depth(layer),
+            radius: circle.radius,
+            red: material.red,
+            green: material.green,
+            blue: material.blue,
+            alpha: material.alpha,
+            outline_width,
+            outline_red,
+            outline_green,
+            outline_blue,
+            outline_alpha,
+        };
+        // even an opaque circle blends along its anti-aliased edge
+        circles_to_draw.push((layer, material.blend_mode, gpu_circle));
     }
+    // back to front, and otherwise in query order
+    circles_to_draw.sort_by_key(|&(layer, _, _)| layer);
 
-    let circle_count = circle_count.try_into().unwrap();
-    if anything_changed || circle_count != renderer.circle_count {
-        renderer.circle_count = circle_count;
-        let buffer = buffer.into_inner();
-
-        let required_buffer_size: wgpu::BufferAddress = buffer.len().try_into().unwrap();
-        if required_buffer_size > renderer.circle_buffer_size {
-            renderer.circle_buffer_size = required_buffer_size;
-            renderer.circle_buffer = renderer.device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("Circle Storage Buffer"),
-                size: renderer.circle_buffer_size,
-                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::STORAGE,
-                mapped_at_creation: false,
-            });
-            renderer.circle_bind_group =
-                renderer
-                    .device
-                    .create_bind_group(&wgpu::BindGroupDescriptor {
-                        label: Some("Circle Bind Group"),
-                        layout: &renderer.circle_bind_group_layout,
-                        entries: &[wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: renderer.circle_buffer.as_entire_binding(),
-                        }],
-                    });
-        }
+    replace_instances::<_, GpuCircle>(&mut instances, anything_changed, &[], &circles_to_draw);
+}
 
-        renderer
-            .queue
-            .write_buffer(&renderer.circle_buffer, 0, &buffer);
+// TODO: find a way to only upload changed circles
+fn update_circles(mut renderer: ResMut<'_, Renderer>, circles: Res<'_, ShapeInstances<Circle>>) {
+    if !circles.is_changed() {
+        return;
+    }
+
+    let required_buffer_size: wgpu::BufferAddress = circles.buffer.len().try_into().unwrap();
+    if required_buffer_size > renderer.circle_buffer_size {
+        renderer.circle_buffer_size = required_buffer_size;
+        renderer.circle_buffer = renderer.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Circle Storage Buffer"),
+            size: renderer.circle_buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+        renderer.circle_bind_group =
+            renderer
+                .device
+                .create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("Circle Bind Group"),
+                    layout: &renderer.circle_bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: renderer.circle_buffer.as_entire_binding(),
+                    }],
+                });
     }
+
+    renderer
+        .queue
+        .write_buffer(&renderer.circle_buffer, 0, &circles.buffer);
 }
 
-fn render(renderer: ResMut<'_, Renderer>) {
+fn render(
+    renderer: ResMut<'_, Renderer>,
+    quads: Res<'_, ShapeInstances<Quad>>,
+    circles: Res<'_, ShapeInstances<Circle>>,
+) {
     let output = loop {
         match renderer.surface.get_current_texture() {
             Ok(output) => break output,
@@ -553,19 +927,233 @@ fn render(renderer: ResMut<'_, Renderer>) {
                     store: true,
                 },
             })],
-            depth_stencil_attachment: None,
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &renderer.depth_texture_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: false,
+                }),
+                stencil_ops: None,
+            }),
         });
 
+        // the depth buffer sorts the opaque quads
         render_pass.set_pipeline(&renderer.quad_render_pipeline);
         render_pass.set_bind_group(0, &renderer.camera_bind_group, &[]);
         render_pass.set_bind_group(1, &renderer.quad_bind_group, &[]);
-        render_pass.draw(0..4, 0..renderer.quad_count);
+        render_pass.draw(0..4, 0..quads.opaque_count);
 
-        render_pass.set_pipeline(&renderer.circle_render_pipeline);
-        render_pass.set_bind_group(1, &renderer.circle_bind_group, &[]);
-        render_pass.draw(0..4, 0..renderer.circle_count);
+        // but translucent shapes have to blend over everything behind them
+        for batch in translucent_batches(
+            &quads.translucent,
+            quads.opaque_count,
+            &circles.translucent,
+            circles.opaque_count,
+        ) {
+            let (pipelines, bind_group) = match batch.shape {
+                Shape::Quad => (
+                    &renderer.translucent_quad_render_pipelines,
+                    &renderer.quad_bind_group,
+                ),
+                Shape::Circle => (
+                    &renderer.circle_render_pipelines,
+                    &renderer.circle_bind_group,
+                ),
+            };
+            render_pass.set_pipeline(&pipelines[batch.blend_mode]);
+            render_pass.set_bind_group(1, bind_group, &[]);
+            render_pass.draw(0..4, batch.instances);
+        }
     }
     renderer.queue.submit([encoder.finish()]);
 
     output.present();
 }
+
+#[test]
+fn layer_depths() {
+    assert_eq!(layer_depth(Layer(i16::MIN)), 1.0);
+    assert!(layer_depth(Layer(i16::MAX)) > 0.0);
+    for layer in [i16::MIN, -1, 0, 1, i16::MAX - 1] {
+        assert!(layer_depth(Layer(layer + 1)) < layer_depth(Layer(layer)));
+    }
+}
+
+#[test]
+fn camera_transform() {
+    use crate::Transform;
+
+    let camera = Camera {
+        vertical_height: 2.0,
+    };
+    let camera_transform = GlobalTransform::from(
+        Transform::from_xy(1.0, 0.0).with_rotation(std::f32::consts::FRAC_PI_2),
+    );
+    let world_to_clip = world_to_clip(&camera_transform, &camera, 2.0);
+    let project = |x, y| world_to_clip.transform_point2(Vec2::new(x, y));
+    assert!(project(1.0, 0.0).abs_diff_eq(Vec2::ZERO, 1e-6));
+    // up on the screen is the camera's up, which is world left
+    assert!(project(0.0, 0.0).abs_diff_eq(Vec2::new(0.0, 1.0), 1e-6));
+    assert!(project(1.0, 2.0).abs_diff_eq(Vec2::new(1.0, 0.0), 1e-6));
+
+    // the uniform has stricter layout rules than storage buffers
+    <GpuCamera as encase::ShaderType>::assert_uniform_compat();
+}
+
+#[test]
+fn quad_transform() {
+    use crate::Transform;
+
+    let quad = Quad {
+        width: 2.0,
+        height: 1.0,
+    };
+    let global_transform = GlobalTransform::from(
+        Transform::from_xy(1.0, 2.0)
+            .with_rotation(std::f32::consts::FRAC_PI_2)
+            .with_scale_xy(2.0, 1.0),
+    );
+    let transform = GpuTransform::from(&global_transform);
+    // the same as `apply_transform` in the shaders
+    let apply_transform = |x: f32, y: f32| {
+        Vec2::new(transform.translation_x, transform.translation_y)
+            + Vec2::new(transform.x_axis_x, transform.x_axis_y) * x
+            + Vec2::new(transform.y_axis_x, transform.y_axis_y) * y
+    };
+    let corner = |x: f32, y: f32| apply_transform(x * quad.width * 0.5, y * quad.height * 0.5);
+    // stretched along its own x axis, which is then turned to point up
+    assert!(corner(1.0, 1.0).abs_diff_eq(Vec2::new(0.5, 4.0), 1e-6));
+    assert!(corner(-1.0, 1.0).abs_diff_eq(Vec2::new(0.5, 0.0), 1e-6));
+    assert!(corner(-1.0, -1.0).abs_diff_eq(Vec2::new(1.5, 0.0), 1e-6));
+    assert!(corner(1.0, -1.0).abs_diff_eq(Vec2::new(1.5, 4.0), 1e-6));
+}
+
+#[test]
+fn removed_shape_components() {
+    use bevy::prelude::{Schedule, World};
+
+    #[derive(Resource, Default)]
+    struct AnyRemoved(bool);
+
+    let mut world = World::new();
+    world.init_resource::<AnyRemoved>();
+    let mut schedule = Schedule::new();
+    schedule.add_systems(
+        |mut removed: RemovedShapeComponents<'_, '_>, mut any_removed: ResMut<'_, AnyRemoved>| {
+            any_removed.0 = removed.any();
+        },
+    );
+    let mut run = |world: &mut World| {
+        schedule.run(world);
+        world.resource::<AnyRemoved>().0
+    };
+
+    let entity = world
+        .spawn((Material::WHITE, Layer(1), Outline::rgb(0.1, 1.0, 1.0, 1.0)))
+        .id();
+    assert!(!run(&mut world));
+    world.entity_mut(entity).remove::<Material>();
+    assert!(run(&mut world));
+    // each removal is only seen once
+    assert!(!run(&mut world));
+    world.entity_mut(entity).remove::<(Layer, Outline)>();
+    assert!(run(&mut world));
+}
+
+#[test]
+fn outline_opacity() {
+    let outline = Outline::rgb(0.1, 1.0, 1.0, 1.0);
+    assert!(is_opaque(&Material::WHITE, None));
+    assert!(is_opaque(&Material::WHITE, Some(&outline)));
+    // a translucent outline has to blend even over an opaque material
+    let translucent_outline = Outline::rgba(0.1, 1.0, 1.0, 1.0, 0.5);
+    assert!(!is_opaque(&Material::WHITE, Some(&translucent_outline)));
+    assert!(!is_opaque(&Material::WHITE.with_alpha(0.5), Some(&outline)));
+}
+
+#[test]
+fn translucent_batching() {
+    use BlendMode::{Additive, Alpha};
+
+    let quads = [
+        (Layer(0), Alpha),
+        (Layer(0), Alpha),
+        (Layer(1), Additive),
+        (Layer(2), Alpha),
+    ];
+    let circles = [
+        (Layer(0), Alpha),
+        (Layer(1), Additive),
+        (Layer(1), Additive),
+    ];
+    let batch = |shape, blend_mode, instances| TranslucentBatch {
+        shape,
+        blend_mode,
+        instances,
+    };
+    assert_eq!(
+        translucent_batches(&quads, 3, &circles, 0),
+        [
+            batch(Shape::Quad, Alpha, 3..5),
+            batch(Shape::Circle, Alpha, 0..1),
+            batch(Shape::Quad, Additive, 5..6),
+            batch(Shape::Circle, Additive, 1..3),
+            batch(Shape::Quad, Alpha, 6..7),
+        ]
+    );
+    assert!(translucent_batches(&[], 0, &[], 5).is_empty());
+}
+
+#[test]
+fn translucent_instances_follow_the_query_order() {
+    use crate::Transform;
+    use bevy::prelude::{Component, Schedule, World};
+
+    #[derive(Component)]
+    struct Unrelated;
+
+    let mut world = World::new();
+    world.init_resource::<Time>();
+    world.init_resource::<ShapeInstances<Quad>>();
+    let mut schedule = Schedule::new();
+    schedule.add_systems(prepare_quads);
+
+    // the red channel tells the quads apart in the uploaded buffer
+    let blend_modes = [BlendMode::Alpha, BlendMode::Additive, BlendMode::Multiply];
+    let global_transform = GlobalTransform::from(Transform::from_xy(0.0, 0.0));
+    let quad = Quad {
+        width: 1.0,
+        height: 1.0,
+    };
+    let translucent = blend_modes.map(|blend_mode| {
+        let red = blend_mode as u8 as f32;
+        let material = Material::rgba(red, 0.0, 0.0, 0.5).with_blend_mode(blend_mode);
+        world.spawn((global_transform, quad, material)).id()
+    });
+    world.spawn((global_transform, quad));
+
+    let mut run = |world: &mut World| {
+        schedule.run(world);
+        let instances = world.resource::<ShapeInstances<Quad>>();
+        assert_eq!(instances.opaque_count, 1);
+        let mut buffer = DynamicStorageBuffer::new(&instances.buffer);
+        buffer.create::<GpuQuad>().unwrap();
+        let uploaded = instances
+            .translucent
+            .iter()
+            .map(|_| blend_modes[buffer.create::<GpuQuad>().unwrap().red as usize])
+            .collect::<Vec<_>>();
+        // every instance is drawn with its own blend mode
+        assert!(uploaded.iter().eq(instances
+            .translucent
+            .iter()
+            .map(|(_, blend_mode)| blend_mode)));
+        uploaded
+    };
+
+    let before = run(&mut world);
+    // moves the quad to another archetype, and so elsewhere in the query, without changing it
+    world.entity_mut(translucent[0]).insert(Unrelated);
+    let after = run(&mut world);
+    assert_ne!(before, after);
+}
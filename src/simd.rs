@@ -0,0 +1,90 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// `N` `f32`s that every operation is applied to lane by lane
+///
+/// This is written with plain arrays so that it works on stable, the loops are simple enough for
+/// the compiler to turn them into SIMD instructions
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(transparent)]
+pub struct F32Lanes<const N: usize>(pub [f32; N]);
+
+pub type F32x4 = F32Lanes<4>;
+pub type F32x8 = F32Lanes<8>;
+
+impl<const N: usize> F32Lanes<N> {
+    pub const LANES: usize = N;
+
+    #[inline]
+    pub const fn splat(value: f32) -> Self {
+        Self([value; N])
+    }
+
+    #[inline]
+    fn map(self, f: impl Fn(f32) -> f32) -> Self {
+        Self(self.0.map(f))
+    }
+
+    #[inline]
+    fn zip(self, other: Self, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self(std::array::from_fn(|i| f(self.0[i], other.0[i])))
+    }
+}
+
+impl<const N: usize> Default for F32Lanes<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::splat(0.0)
+    }
+}
+
+impl<const N: usize> From<[f32; N]> for F32Lanes<N> {
+    #[inline]
+    fn from(lanes: [f32; N]) -> Self {
+        Self(lanes)
+    }
+}
+
+impl<const N: usize> Neg for F32Lanes<N> {
+    type Output = F32Lanes<N>;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        self.map(|a| -a)
+    }
+}
+
+impl<const N: usize> Add for F32Lanes<N> {
+    type Output = F32Lanes<N>;
+
+    #[inline]
+    fn add(self, rhs: F32Lanes<N>) -> Self::Output {
+        self.zip(rhs, |a, b| a + b)
+    }
+}
+
+impl<const N: usize> Sub for F32Lanes<N> {
+    type Output = F32Lanes<N>;
+
+    #[inline]
+    fn sub(self, rhs: F32Lanes<N>) -> Self::Output {
+        self.zip(rhs, |a, b| a - b)
+    }
+}
+
+impl<const N: usize> Mul for F32Lanes<N> {
+    type Output = F32Lanes<N>;
+
+    #[inline]
+    fn mul(self, rhs: F32Lanes<N>) -> Self::Output {
+        self.zip(rhs, |a, b| a * b)
+    }
+}
+
+impl<const N: usize> Div for F32Lanes<N> {
+    type Output = F32Lanes<N>;
+
+    #[inline]
+    fn div(self, rhs: F32Lanes<N>) -> Self::Output {
+        self.zip(rhs, |a, b| a / b)
+    }
+}
@@ -0,0 +1,241 @@
+//! Frame timing and the fixed timestep that gameplay runs on
+//!
+//! [`FixedUpdate`] runs zero or more times per frame, once for every [`Time::fixed_timestep`] that
+//! has passed, so anything simulated there behaves the same at any frame rate
+
+use bevy::{app::RunFixedUpdateLoop, ecs::schedule::ScheduleLabel, prelude::*};
+use std::time::{Duration, Instant};
+
+/// Runs after every [`FixedUpdate`], for work that needs the result of the whole step
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FixedPostUpdate;
+
+/// How much time has passed, for the current frame or, inside [`FixedUpdate`], the current step
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct Time {
+    last_update: Option<Instant>,
+    frame_delta: Duration,
+    frame_elapsed: Duration,
+    fixed_elapsed: Duration,
+    accumulator: Duration,
+    fixed_timestep: Duration,
+    max_delta: Duration,
+    scale: f64,
+    paused: bool,
+    in_fixed_step: bool,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self {
+            last_update: None,
+            frame_delta: Duration::ZERO,
+            frame_elapsed: Duration::ZERO,
+            fixed_elapsed: Duration::ZERO,
+            accumulator: Duration::ZERO,
+            fixed_timestep: Duration::from_secs(1) / 60,
+            max_delta: Duration::from_millis(250),
+            scale: 1.0,
+            paused: false,
+            in_fixed_step: false,
+        }
+    }
+}
+
+impl Time {
+    /// The scaled time since the last frame, or the fixed timestep inside [`FixedUpdate`]
+    #[inline]
+    pub fn delta(&self) -> Duration {
+        if self.in_fixed_step {
+            self.fixed_timestep
+        } else {
+            self.frame_delta
+        }
+    }
+
+    #[inline]
+    pub fn delta_seconds(&self) -> f32 {
+        self.delta().as_secs_f32()
+    }
+
+    /// The scaled time since startup, which inside [`FixedUpdate`] only counts whole steps
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        if self.in_fixed_step {
+            self.fixed_elapsed
+        } else {
+            self.frame_elapsed
+        }
+    }
+
+    #[inline]
+    pub fn elapsed_seconds(&self) -> f64 {
+        self.elapsed().as_secs_f64()
+    }
+
+    #[inline]
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Makes time pass `scale` times as fast, for slow motion or fast forward
+    pub fn set_scale(&mut self, scale: f64) {
+        assert!(
+            scale.is_finite() && scale >= 0.0,
+            "time scale must be finite and not negative, got {scale}"
+        );
+        self.scale = scale;
+    }
+
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stops time from passing, so [`FixedUpdate`] stops running and the delta is zero
+    #[inline]
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    #[inline]
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    #[inline]
+    pub fn fixed_timestep(&self) -> Duration {
+        self.fixed_timestep
+    }
+
+    pub fn set_fixed_timestep(&mut self, fixed_timestep: Duration) {
+        assert!(!fixed_timestep.is_zero(), "fixed timestep must not be zero");
+        self.fixed_timestep = fixed_timestep;
+    }
+
+    #[inline]
+    pub fn max_delta(&self) -> Duration {
+        self.max_delta
+    }
+
+    /// Limits how much time a single frame can advance, so a long stall doesn't have to be caught
+    /// up on with a burst of fixed steps
+    #[inline]
+    pub fn set_max_delta(&mut self, max_delta: Duration) {
+        self.max_delta = max_delta;
+    }
+
+    /// How far the current frame is into the next fixed step, from 0 up to 1
+    #[inline]
+    pub fn overstep_fraction(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.fixed_timestep.as_secs_f32()
+    }
+
+    /// Whether this is read from inside [`FixedUpdate`] or [`FixedPostUpdate`]
+    #[inline]
+    pub fn in_fixed_step(&self) -> bool {
+        self.in_fixed_step
+    }
+
+    /// Starts a new frame at `now`, which is done once per frame by [`TimePlugin`]
+    pub fn update_with_instant(&mut self, now: Instant) {
+        let raw_delta = self.last_update.map_or(Duration::ZERO, |last_update| {
+            now.saturating_duration_since(last_update)
+        });
+        self.last_update = Some(now);
+
+        self.frame_delta = if self.paused {
+            Duration::ZERO
+        } else {
+            raw_delta.min(self.max_delta).mul_f64(self.scale)
+        };
+        self.frame_elapsed += self.frame_delta;
+        self.accumulator += self.frame_delta;
+    }
+
+    /// Takes a fixed step out of the accumulated time, returning whether there was enough for one
+    fn expend_fixed_step(&mut self) -> bool {
+        self.in_fixed_step = !self.paused && self.accumulator >= self.fixed_timestep;
+        if self.in_fixed_step {
+            self.accumulator -= self.fixed_timestep;
+            self.fixed_elapsed += self.fixed_timestep;
+        }
+        self.in_fixed_step
+    }
+}
+
+pub struct TimePlugin;
+
+impl Plugin for TimePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Time>()
+            .init_schedule(FixedUpdate)
+            .init_schedule(FixedPostUpdate)
+            .add_systems(First, update_time)
+            .add_systems(RunFixedUpdateLoop, run_fixed_update);
+    }
+}
+
+fn update_time(mut time: ResMut<'_, Time>) {
+    time.update_with_instant(Instant::now());
+}
+
+fn run_fixed_update(world: &mut World) {
+    while world.resource_mut::<Time>().expend_fixed_step() {
+        world.run_schedule(FixedUpdate);
+        world.run_schedule(FixedPostUpdate);
+    }
+}
+
+#[test]
+fn fixed_timestep() {
+    let start = Instant::now();
+    let mut time = Time::default();
+    time.set_fixed_timestep(Duration::from_millis(10));
+    time.update_with_instant(start);
+    assert_eq!(time.delta(), Duration::ZERO);
+
+    time.update_with_instant(start + Duration::from_millis(25));
+    assert_eq!(time.delta(), Duration::from_millis(25));
+    assert!(time.expend_fixed_step());
+    assert_eq!(time.delta(), Duration::from_millis(10));
+    assert!(time.expend_fixed_step());
+    assert_eq!(time.elapsed(), Duration::from_millis(20));
+    assert!(!time.expend_fixed_step());
+    assert_eq!(time.delta(), Duration::from_millis(25));
+    assert_eq!(time.elapsed(), Duration::from_millis(25));
+    assert!((time.overstep_fraction() - 0.5).abs() < 1e-6);
+
+    // the leftover carries over into the next frame
+    time.update_with_instant(start + Duration::from_millis(30));
+    assert!(time.expend_fixed_step());
+    assert!(!time.expend_fixed_step());
+    assert_eq!(time.overstep_fraction(), 0.0);
+
+    // a long stall is clamped
+    time.update_with_instant(start + Duration::from_secs(10));
+    assert_eq!(time.delta(), time.max_delta());
+}
+
+#[test]
+fn scale_and_pause() {
+    let start = Instant::now();
+    let mut time = Time::default();
+    time.update_with_instant(start);
+
+    time.set_scale(2.0);
+    time.update_with_instant(start + Duration::from_millis(10));
+    assert_eq!(time.delta(), Duration::from_millis(20));
+    assert_eq!(time.elapsed(), Duration::from_millis(20));
+
+    time.pause();
+    time.update_with_instant(start + Duration::from_millis(20));
+    assert_eq!(time.delta(), Duration::ZERO);
+    assert_eq!(time.elapsed(), Duration::from_millis(20));
+    assert!(!time.expend_fixed_step());
+
+    time.unpause();
+    time.set_scale(0.5);
+    time.update_with_instant(start + Duration::from_millis(40));
+    assert_eq!(time.delta(), Duration::from_millis(10));
+}